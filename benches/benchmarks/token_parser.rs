@@ -1,7 +1,7 @@
 use std::hint::black_box;
 
 use criterion::{Criterion, Throughput, criterion_group};
-use kusto_language::token_parser::{ParseOptions, parse_tokens};
+use kusto_language::token_parser::{ParseOptions, Token, parse_tokens};
 
 fn build_long_input() -> String {
     let base = concat!(
@@ -69,6 +69,27 @@ fn build_long_input() -> String {
     input
 }
 
+fn build_keyword_dense_input() -> String {
+    let base = concat!(
+        "T | where a has 'x' and b !has 'y' or c contains_cs 'z' and d !startswith 'w'\n",
+        "| project-away c1 | project-rename n = o | project-reorder a asc, b granny-desc\n",
+        "| summarize hint.strategy=shuffle hint.num_partitions=10 count() by bin(ts, 1h)\n",
+        "| join kind=inner hint.remote=auto (U | mv-expand x | make-series y on z) on k\n",
+        "| where s in~ ('a') and t !in ('b') and u has_any ('c') and v matches regex 'd'\n",
+        "| materialized-view-combine | sample-distinct 10 of id | top-nested 3 of a\n",
+    );
+
+    base.repeat(200)
+}
+
+// Baseline that allocates every token's text, as the lexer did before tokens borrowed the source
+fn parse_owned_tokens(text: &str, options: &ParseOptions) -> Vec<Token<'static>> {
+    parse_tokens(text, options)
+        .into_iter()
+        .map(Token::into_owned)
+        .collect()
+}
+
 fn token_parser_tests(c: &mut Criterion) {
     let options = ParseOptions::default();
 
@@ -83,6 +104,12 @@ fn token_parser_tests(c: &mut Criterion) {
             black_box(tokens.len());
         });
     });
+    short.bench_function("parse_owned", |b| {
+        b.iter(|| {
+            let tokens = parse_owned_tokens(black_box(short_input), black_box(&options));
+            black_box(tokens.len());
+        });
+    });
     short.finish();
 
     // ---- Long input ----
@@ -96,7 +123,26 @@ fn token_parser_tests(c: &mut Criterion) {
             black_box(tokens.len());
         });
     });
+    long.bench_function("parse_owned", |b| {
+        b.iter(|| {
+            let tokens = parse_owned_tokens(black_box(&long_input), black_box(&options));
+            black_box(tokens.len());
+        });
+    });
     long.finish();
+
+    // ---- Keyword-dense input ----
+    let keywords_input = build_keyword_dense_input();
+    let mut keywords = c.benchmark_group("token_parser/keywords");
+
+    keywords.throughput(Throughput::Bytes(keywords_input.len() as u64));
+    keywords.bench_function("parse", |b| {
+        b.iter(|| {
+            let tokens = parse_tokens(black_box(&keywords_input), black_box(&options));
+            black_box(tokens.len());
+        });
+    });
+    keywords.finish();
 }
 
 criterion_group!(token_parser_benches, token_parser_tests);
@@ -1,2 +1,2 @@
+pub mod syntax_parser;
 pub mod token_parser;
-pub mod syntax_parser;
\ No newline at end of file
@@ -1,4 +1,7 @@
-use crate::token_parser::KeywordKind;
+use crate::{
+    syntax_parser::{BinOpKind, HintKind, JoinKind},
+    token_parser::KeywordKind,
+};
 
 pub(crate) const EXTENDED_KEYWORDS_AS_IDENTIFIERS: &[(KeywordKind, &str)] = &[
     (KeywordKind::PartitionBy, "__partitionby"),
@@ -95,3 +98,85 @@ pub(crate) const EXTENDED_KEYWORDS_AS_IDENTIFIERS: &[(KeywordKind, &str)] = &[
     (KeywordKind::With, "with"),
     (KeywordKind::WithNodeId, "with_node_id"),
 ];
+
+pub(crate) const STRING_OPERATORS: &[(KeywordKind, BinOpKind)] = &[
+    (KeywordKind::Has, BinOpKind::Has),
+    (KeywordKind::HasCs, BinOpKind::HasCs),
+    (KeywordKind::NotHas, BinOpKind::NotHas),
+    (KeywordKind::NotHasCs, BinOpKind::NotHasCs),
+    (KeywordKind::HasPrefix, BinOpKind::HasPrefix),
+    (KeywordKind::HasPrefixCs, BinOpKind::HasPrefixCs),
+    (KeywordKind::NotHasPrefix, BinOpKind::NotHasPrefix),
+    (KeywordKind::NotHasPrefixCs, BinOpKind::NotHasPrefixCs),
+    (KeywordKind::HasSuffix, BinOpKind::HasSuffix),
+    (KeywordKind::HasSuffixCs, BinOpKind::HasSuffixCs),
+    (KeywordKind::NotHasSuffix, BinOpKind::NotHasSuffix),
+    (KeywordKind::NotHasSuffixCs, BinOpKind::NotHasSuffixCs),
+    (KeywordKind::Contains, BinOpKind::Contains),
+    (KeywordKind::ContainsCs, BinOpKind::ContainsCs),
+    (KeywordKind::ContainsCs2, BinOpKind::ContainsCs),
+    (KeywordKind::NotContains, BinOpKind::NotContains),
+    (KeywordKind::NotBangContains, BinOpKind::NotContains),
+    (KeywordKind::NotContainsCs, BinOpKind::NotContainsCs),
+    (KeywordKind::NotBangContainsCs, BinOpKind::NotContainsCs),
+    (KeywordKind::StartsWith, BinOpKind::StartsWith),
+    (KeywordKind::StartsWithCs, BinOpKind::StartsWithCs),
+    (KeywordKind::NotStartsWith, BinOpKind::NotStartsWith),
+    (KeywordKind::NotStartsWithCs, BinOpKind::NotStartsWithCs),
+    (KeywordKind::EndsWith, BinOpKind::EndsWith),
+    (KeywordKind::EndsWithCs, BinOpKind::EndsWithCs),
+    (KeywordKind::NotEndsWith, BinOpKind::NotEndsWith),
+    (KeywordKind::NotEndsWithCs, BinOpKind::NotEndsWithCs),
+    (KeywordKind::MatchesRegex, BinOpKind::MatchesRegex),
+    (KeywordKind::Like, BinOpKind::Like),
+    (KeywordKind::LikeCs, BinOpKind::LikeCs),
+    (KeywordKind::NotLike, BinOpKind::NotLike),
+    (KeywordKind::NotLikeCs, BinOpKind::NotLikeCs),
+];
+
+pub(crate) const HINTS: &[(KeywordKind, &str, HintKind)] = &[
+    (
+        KeywordKind::HintDotNumPartitions,
+        "hint.num_partitions",
+        HintKind::NumPartitions,
+    ),
+    (KeywordKind::HintDotRemote, "hint.remote", HintKind::Remote),
+    (
+        KeywordKind::HintDotShuffleKey,
+        "hint.shufflekey",
+        HintKind::ShuffleKey,
+    ),
+    (
+        KeywordKind::HintDotStrategy,
+        "hint.strategy",
+        HintKind::Strategy,
+    ),
+];
+
+pub(crate) const SUMMARIZE_HINTS: &[HintKind] = &[
+    HintKind::NumPartitions,
+    HintKind::ShuffleKey,
+    HintKind::Strategy,
+];
+
+pub(crate) const JOIN_HINTS: &[HintKind] = &[
+    HintKind::NumPartitions,
+    HintKind::Remote,
+    HintKind::ShuffleKey,
+    HintKind::Strategy,
+];
+
+pub(crate) const JOIN_KINDS: &[(&str, JoinKind)] = &[
+    ("anti", JoinKind::LeftAnti),
+    ("fullouter", JoinKind::FullOuter),
+    ("inner", JoinKind::Inner),
+    ("innerunique", JoinKind::InnerUnique),
+    ("leftanti", JoinKind::LeftAnti),
+    ("leftantisemi", JoinKind::LeftAnti),
+    ("leftouter", JoinKind::LeftOuter),
+    ("leftsemi", JoinKind::LeftSemi),
+    ("rightanti", JoinKind::RightAnti),
+    ("rightantisemi", JoinKind::RightAnti),
+    ("rightouter", JoinKind::RightOuter),
+    ("rightsemi", JoinKind::RightSemi),
+];
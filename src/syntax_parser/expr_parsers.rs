@@ -1,31 +1,168 @@
 use crate::{
-    parser_return,
+    parser_arg, parser_return,
     syntax_parser::{
-        constants::EXTENDED_KEYWORDS_AS_IDENTIFIERS, lit_parsers::*, types::*, utilities::*,
+        constants::{EXTENDED_KEYWORDS_AS_IDENTIFIERS, STRING_OPERATORS},
+        lit_parsers::*,
+        operator_parsers::*,
+        types::*,
+        utilities::*,
     },
-    token_parser::{KeywordKind, PunctuationKind, TokenKind},
+    token_parser::{KEYWORDS, KeywordKind, PunctuationKind, TokenKind},
 };
 use chumsky::{prelude::*, primitive::select};
+use std::ops::Range;
 
-// TODO: In, InCs, NotIn, NotInCs, HasAny, HasAll, Between and NotBetween operators to be added later
 // TODO: Start expression ( * == value) to be added later
 
-pub(crate) fn lit_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn lit_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
     boolean_lit()
+        .or(datetime_lit())
+        .or(decimal_lit())
+        .or(dynamic_lit())
+        .or(guid_lit())
+        .or(int_lit())
         .or(long_lit())
         .or(real_lit())
         .or(string_lit())
+        .or(timespan_lit())
         .map(ExprKind::Literal)
+        .boxed()
 }
 
-pub(crate) fn multiplicative_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn name_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    ext_name().map(ExprKind::Name)
+}
+
+pub(crate) fn function_call_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let arg = named_arg_expr(expr.clone())
+        .or(expr)
+        .or(punct_token(PunctuationKind::Asterisk).to(ExprKind::Star))
+        .map_with(|arg, e| {
+            let span: Range<usize> = e.span();
+            (arg, span)
+        });
+
+    iden_name()
+        .or(ext_kw_as_iden_name())
+        .then(
+            arg.separated_by(punct_token(PunctuationKind::Comma))
+                .collect::<Vec<_>>()
+                .delimited_by(
+                    punct_token(PunctuationKind::OpenParen),
+                    punct_token(PunctuationKind::CloseParen),
+                ),
+        )
+        .validate(|(name, args), _, emitter| {
+            let last = args.len().saturating_sub(1);
+            for (_, span) in args[..last]
+                .iter()
+                .filter(|(arg, _)| *arg == ExprKind::Star)
+            {
+                emitter.emit(Rich::custom(
+                    span.clone(),
+                    "'*' is only allowed as the last argument",
+                ));
+            }
+
+            ExprKind::FunctionCall {
+                name,
+                args: args.into_iter().map(|(arg, _)| arg).collect(),
+            }
+        })
+}
+
+pub(crate) fn primary_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let paren = expr
+        .clone()
+        .delimited_by(
+            punct_token(PunctuationKind::OpenParen),
+            punct_token(PunctuationKind::CloseParen),
+        )
+        .map(|expr| ExprKind::Paren(Box::new(expr)));
+
     lit_expr()
+        .or(paren)
+        .or(function_call_expr(expr))
+        .or(name_expr())
+        .boxed()
+}
+
+pub(crate) fn postfix_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let member = punct_token(PunctuationKind::Dot)
+        .ignore_then(function_call_expr(expr.clone()).or(name_expr()))
+        .map(|selector| (selector, false));
+    let element = expr
+        .clone()
+        .delimited_by(
+            punct_token(PunctuationKind::OpenBracket),
+            punct_token(PunctuationKind::CloseBracket),
+        )
+        .map(|index| (index, true));
+
+    primary_expr(expr)
+        .foldl(
+            member.or(element).repeated(),
+            |expr, (selector, is_element)| {
+                if is_element {
+                    ExprKind::ElementAccess {
+                        expr: Box::new(expr),
+                        index: Box::new(selector),
+                    }
+                } else {
+                    ExprKind::Path {
+                        expr: Box::new(expr),
+                        selector: Box::new(selector),
+                    }
+                }
+            },
+        )
+        .boxed()
+}
+
+pub(crate) fn unary_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let not = iden_name()
+        .filter(|name| name == "not")
+        .ignore_then(expr.clone().delimited_by(
+            punct_token(PunctuationKind::OpenParen),
+            punct_token(PunctuationKind::CloseParen),
+        ))
+        .map(|expr| ExprKind::UnaryOp {
+            op: UnaryOpKind::Not,
+            expr: Box::new(expr),
+        });
+
+    punct_token(PunctuationKind::Plus)
+        .to(UnaryOpKind::Plus)
+        .or(punct_token(PunctuationKind::Minus).to(UnaryOpKind::Minus))
+        .repeated()
+        .foldr(not.or(postfix_expr(expr)), |op, expr| ExprKind::UnaryOp {
+            op,
+            expr: Box::new(expr),
+        })
+        .boxed()
+}
+
+pub(crate) fn multiplicative_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let operand = unary_expr(expr);
+
+    operand
+        .clone()
         .then(
             punct_token(PunctuationKind::Asterisk)
                 .to(BinOpKind::Multiply)
                 .or(punct_token(PunctuationKind::Slash).to(BinOpKind::Divide))
                 .or(punct_token(PunctuationKind::Percent).to(BinOpKind::Modulo))
-                .then(lit_expr())
+                .then(operand)
                 .repeated()
                 .collect::<Vec<_>>(),
         )
@@ -37,15 +174,21 @@ pub(crate) fn multiplicative_expr<'a>() -> parser_return!(ExprKind) {
                     right: Box::new(right),
                 })
         })
+        .boxed()
 }
 
-pub(crate) fn additive_expr<'a>() -> parser_return!(ExprKind) {
-    multiplicative_expr()
+pub(crate) fn additive_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let operand = multiplicative_expr(expr);
+
+    operand
+        .clone()
         .then(
             punct_token(PunctuationKind::Plus)
                 .to(BinOpKind::Add)
                 .or(punct_token(PunctuationKind::Minus).to(BinOpKind::Subtract))
-                .then(multiplicative_expr())
+                .then(operand)
                 .repeated()
                 .collect::<Vec<_>>(),
         )
@@ -57,10 +200,16 @@ pub(crate) fn additive_expr<'a>() -> parser_return!(ExprKind) {
                     right: Box::new(right),
                 })
         })
+        .boxed()
 }
 
-pub(crate) fn relational_expr<'a>() -> parser_return!(ExprKind) {
-    additive_expr()
+pub(crate) fn relational_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let operand = additive_expr(expr);
+
+    operand
+        .clone()
         .then(
             punct_token(PunctuationKind::LessThan)
                 .to(BinOpKind::LessThan)
@@ -68,7 +217,7 @@ pub(crate) fn relational_expr<'a>() -> parser_return!(ExprKind) {
                 .or(punct_token(PunctuationKind::GreaterThan).to(BinOpKind::GreaterThan))
                 .or(punct_token(PunctuationKind::GreaterThanOrEqual)
                     .to(BinOpKind::GreaterThanOrEqual))
-                .then(additive_expr())
+                .then(operand)
                 .repeated()
                 .collect::<Vec<_>>(),
         )
@@ -80,16 +229,87 @@ pub(crate) fn relational_expr<'a>() -> parser_return!(ExprKind) {
                     right: Box::new(right),
                 })
         })
+        .boxed()
+}
+
+pub(crate) fn in_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let op = just(TokenKind::Keyword(KeywordKind::In))
+        .to(InOpKind::In)
+        .or(just(TokenKind::Keyword(KeywordKind::InCs)).to(InOpKind::InCs))
+        .or(just(TokenKind::Keyword(KeywordKind::NotIn)).to(InOpKind::NotIn))
+        .or(just(TokenKind::Keyword(KeywordKind::NotInCs)).to(InOpKind::NotInCs))
+        .or(just(TokenKind::Keyword(KeywordKind::HasAny)).to(InOpKind::HasAny))
+        .or(just(TokenKind::Keyword(KeywordKind::HasAll)).to(InOpKind::HasAll));
+    let list = expr
+        .clone()
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .at_least(1)
+        .collect::<Vec<_>>()
+        .delimited_by(
+            punct_token(PunctuationKind::OpenParen),
+            punct_token(PunctuationKind::CloseParen),
+        );
+
+    relational_expr(expr)
+        .then(op.then(list).or_not())
+        .map(|(expr, rest)| match rest {
+            Some((op, list)) => ExprKind::In {
+                expr: Box::new(expr),
+                op,
+                list,
+            },
+            None => expr,
+        })
+        .boxed()
 }
 
-pub(crate) fn equality_expr<'a>() -> parser_return!(ExprKind) {
-    relational_expr()
+pub(crate) fn between_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let negated = just(TokenKind::Keyword(KeywordKind::Between))
+        .to(false)
+        .or(just(TokenKind::Keyword(KeywordKind::NotBetween)).to(true));
+    let range = expr
+        .clone()
+        .then_ignore(punct_token(PunctuationKind::DotDot))
+        .then(expr.clone())
+        .delimited_by(
+            punct_token(PunctuationKind::OpenParen),
+            punct_token(PunctuationKind::CloseParen),
+        );
+
+    in_expr(expr)
+        .then(negated.then(range).or_not())
+        .map(|(expr, rest)| match rest {
+            Some((negated, (low, high))) => ExprKind::Between {
+                expr: Box::new(expr),
+                low: Box::new(low),
+                high: Box::new(high),
+                negated,
+            },
+            None => expr,
+        })
+        .boxed()
+}
+
+pub(crate) fn equality_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let operand = between_expr(expr);
+
+    operand
+        .clone()
         .then(
             punct_token(PunctuationKind::EqualEqual)
                 .to(BinOpKind::Equal)
                 .or(punct_token(PunctuationKind::BangEqual).to(BinOpKind::NotEqual))
                 .or(punct_token(PunctuationKind::LessThanGreaterThan).to(BinOpKind::NotEqual))
-                .then(relational_expr())
+                .or(punct_token(PunctuationKind::EqualTilde).to(BinOpKind::EqualTilde))
+                .or(punct_token(PunctuationKind::BangTilde).to(BinOpKind::BangTilde))
+                .or(string_op())
+                .then(operand)
                 .repeated()
                 .collect::<Vec<_>>(),
         )
@@ -101,13 +321,29 @@ pub(crate) fn equality_expr<'a>() -> parser_return!(ExprKind) {
                     right: Box::new(right),
                 })
         })
+        .boxed()
 }
 
-pub(crate) fn logical_and_expr<'a>() -> parser_return!(ExprKind) {
-    equality_expr()
+pub(crate) fn string_op<'a, I: TokenInput<'a>>() -> parser_return!(BinOpKind) {
+    select(|token, _| match token {
+        TokenKind::Keyword(value) => STRING_OPERATORS
+            .iter()
+            .find(|op| value == op.0)
+            .map(|op| op.1.clone()),
+        _ => None,
+    })
+}
+
+pub(crate) fn logical_and_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let operand = equality_expr(expr);
+
+    operand
+        .clone()
         .then(
             just(TokenKind::Keyword(KeywordKind::And))
-                .ignore_then(equality_expr())
+                .ignore_then(operand)
                 .repeated()
                 .collect::<Vec<_>>(),
         )
@@ -118,13 +354,19 @@ pub(crate) fn logical_and_expr<'a>() -> parser_return!(ExprKind) {
                 right: Box::new(right),
             })
         })
+        .boxed()
 }
 
-pub(crate) fn logical_or_expr<'a>() -> parser_return!(ExprKind) {
-    logical_and_expr()
+pub(crate) fn logical_or_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let operand = logical_and_expr(expr);
+
+    operand
+        .clone()
         .then(
             just(TokenKind::Keyword(KeywordKind::Or))
-                .ignore_then(logical_and_expr())
+                .ignore_then(operand)
                 .repeated()
                 .collect::<Vec<_>>(),
         )
@@ -135,42 +377,126 @@ pub(crate) fn logical_or_expr<'a>() -> parser_return!(ExprKind) {
                 right: Box::new(right),
             })
         })
+        .boxed()
+}
+
+pub(crate) fn unnamed_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    logical_or_expr(pipe_expr())
 }
 
-pub(crate) fn unnamed_expr<'a>() -> parser_return!(ExprKind) {
-    logical_or_expr()
+pub(crate) fn pipe_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    recursive(|expr| {
+        let scalar = logical_or_expr(expr);
+
+        scalar
+            .clone()
+            .foldl(
+                punct_token(PunctuationKind::Bar)
+                    .ignore_then(query_operator(scalar))
+                    .repeated(),
+                |left, operator| ExprKind::Pipe {
+                    left: Box::new(left),
+                    operator,
+                },
+            )
+            .boxed()
+    })
 }
 
-pub(crate) fn iden_name_decl_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn iden_name<'a, I: TokenInput<'a>>() -> parser_return!(String) {
     select(|token, _| match token {
-        TokenKind::Identifier(value) => Some(ExprKind::NameDecl(value)),
+        TokenKind::Identifier(value) => Some(value.into_owned()),
         _ => None,
     })
 }
 
 //TODO: ClientParameterReferenceExpr to be added later
 
-pub(crate) fn bracketed_name_decl_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn bracketed_name<'a, I: TokenInput<'a>>() -> parser_return!(String) {
     punct_token(PunctuationKind::OpenBracket)
         .ignore_then(string_lit())
         .then_ignore(punct_token(PunctuationKind::CloseBracket))
         .map(|lit| match lit {
-            LitExprKind::String(value) => ExprKind::NameDecl(value),
+            LitExprKind::String { value, .. } => value,
             _ => panic!("Bracketed name must be a string literal"),
         })
 }
 
-pub(crate) fn ext_kw_as_iden_name_decl_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn ext_kw_as_iden_name<'a, I: TokenInput<'a>>() -> parser_return!(String) {
     select(|token, _| match token {
         TokenKind::Keyword(value) => EXTENDED_KEYWORDS_AS_IDENTIFIERS
             .iter()
             .find(|kw| value == kw.0)
-            .map(|kw| ExprKind::NameDecl(kw.1.to_string())),
+            .map(|kw| kw.1.to_string()),
+        _ => None,
+    })
+}
+
+pub(crate) fn kw_as_iden_name<'a, I: TokenInput<'a>>() -> parser_return!(String) {
+    select(|token, _| match token {
+        TokenKind::Keyword(value) => KEYWORDS
+            .iter()
+            .find(|kw| value == kw.1)
+            .map(|kw| String::from_utf8_lossy(kw.0).into_owned())
+            .filter(|name| name.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_')),
         _ => None,
     })
 }
 
-pub(crate) fn ext_name_decl_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn ext_name<'a, I: TokenInput<'a>>() -> parser_return!(String) {
+    iden_name().or(bracketed_name()).or(ext_kw_as_iden_name())
+}
+
+pub(crate) fn wildcarded_name_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    let part = iden_name()
+        .or(ext_kw_as_iden_name())
+        .or(punct_token(PunctuationKind::Asterisk).to("*".to_string()))
+        .map_with(|part, e| {
+            let span: Range<usize> = e.span();
+            (part, span)
+        });
+
+    part.repeated()
+        .at_least(1)
+        .collect::<Vec<_>>()
+        .validate(|parts, _, emitter| {
+            for pair in parts.windows(2) {
+                if pair[0].1.end != pair[1].1.start {
+                    emitter.emit(Rich::custom(
+                        pair[1].1.clone(),
+                        "Unexpected whitespace in wildcarded name",
+                    ));
+                }
+            }
+
+            let name = parts.into_iter().map(|(part, _)| part).collect::<String>();
+            if name.contains('*') {
+                ExprKind::WildcardedName(name)
+            } else {
+                ExprKind::Name(name)
+            }
+        })
+}
+
+pub(crate) fn column_name_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    bracketed_name()
+        .map(ExprKind::Name)
+        .or(wildcarded_name_expr())
+}
+
+pub(crate) fn iden_name_decl_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    iden_name().map(ExprKind::NameDecl)
+}
+
+pub(crate) fn bracketed_name_decl_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    bracketed_name().map(ExprKind::NameDecl)
+}
+
+pub(crate) fn ext_kw_as_iden_name_decl_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
+    ext_kw_as_iden_name().map(ExprKind::NameDecl)
+}
+
+pub(crate) fn ext_name_decl_expr<'a, I: TokenInput<'a>>() -> parser_return!(ExprKind) {
     iden_name_decl_expr()
         .or(bracketed_name_decl_expr())
         .or(ext_kw_as_iden_name_decl_expr())
@@ -178,10 +504,42 @@ pub(crate) fn ext_name_decl_expr<'a>() -> parser_return!(ExprKind) {
 
 // TODO: Dashed name decl to be added later
 
-// TODO: Tuple name decl to be added later
-pub(crate) fn named_expr<'a>() -> parser_return!(ExprKind) {
+pub(crate) fn tuple_name_decl<'a, I: TokenInput<'a>>() -> parser_return!(Vec<ExprKind>) {
+    ext_name_decl_expr()
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .at_least(1)
+        .collect::<Vec<_>>()
+        .delimited_by(
+            punct_token(PunctuationKind::OpenParen),
+            punct_token(PunctuationKind::CloseParen),
+        )
+}
+
+pub(crate) fn named_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
+    let simple = ext_name_decl_expr()
+        .then(punct_token(PunctuationKind::Equal).ignore_then(expr.clone()))
+        .map(|(name, expr)| ExprKind::SimpleNamed {
+            name: Box::new(name),
+            expr: Box::new(expr),
+        });
+    let tuple = tuple_name_decl()
+        .then(punct_token(PunctuationKind::Equal).ignore_then(expr))
+        .map(|(names, expr)| ExprKind::TupleNamed {
+            names,
+            expr: Box::new(expr),
+        });
+
+    simple.or(tuple)
+}
+
+pub(crate) fn named_arg_expr<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(ExprKind) {
     ext_name_decl_expr()
-        .then(punct_token(PunctuationKind::Equal).ignore_then(unnamed_expr()))
+        .or(kw_as_iden_name().map(ExprKind::NameDecl))
+        .then(punct_token(PunctuationKind::Equal).ignore_then(expr))
         .map(|(name, expr)| ExprKind::SimpleNamed {
             name: Box::new(name),
             expr: Box::new(expr),
@@ -1,14 +1,16 @@
 use crate::{
     parser_return,
-    syntax_parser::LitExprKind,
-    token_parser::{LiteralKind, TokenKind},
+    syntax_parser::{
+        DecimalValue, DynamicValue, LitExprKind, TokenInput, utilities::*, value_parsers::*,
+    },
+    token_parser::{KeywordKind, LiteralKind, PunctuationKind, TokenKind},
 };
 use chumsky::{prelude::*, primitive::select};
+use std::ops::Range;
 
-// TODO: DateTime, Decimal, Guid, Int, TimeSpan to be added later
-// TODO: Currently we only support literals not goo literals like int(123)
+// TODO: Currently bool, long and real only support plain literals, not goo literals like long(123)
 
-pub(crate) fn boolean_lit<'a>() -> parser_return!(LitExprKind) {
+pub(crate) fn boolean_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
     select(|token, _| match token {
         TokenKind::Literal(LiteralKind::Boolean(value)) => Some(value),
         _ => None,
@@ -27,7 +29,195 @@ pub(crate) fn boolean_lit<'a>() -> parser_return!(LitExprKind) {
     })
 }
 
-pub(crate) fn long_lit<'a>() -> parser_return!(LitExprKind) {
+pub(crate) fn datetime_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
+    select(|token, _| match token {
+        TokenKind::Literal(LiteralKind::DateTime(value)) => Some(value),
+        _ => None,
+    })
+    .validate(|value, e, emitter| {
+        let (offset, text) = get_goo_value(&value);
+        match parse_lit_value(text, e.span(), offset, "datetime", parse_datetime) {
+            Ok(val) => LitExprKind::DateTime(val),
+            Err(err) => {
+                emitter.emit(err);
+                LitExprKind::DateTime(None) // Return a default value
+            }
+        }
+    })
+}
+
+pub(crate) fn decimal_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
+    select(|token, _| match token {
+        TokenKind::Literal(LiteralKind::Decimal(value)) => Some(value),
+        _ => None,
+    })
+    .validate(|value, e, emitter| {
+        let (offset, text) = get_goo_value(&value);
+        match parse_lit_value(text, e.span(), offset, "decimal", parse_decimal) {
+            Ok(val) => LitExprKind::Decimal(val),
+            Err(err) => {
+                emitter.emit(err);
+                LitExprKind::Decimal(None) // Return a default value
+            }
+        }
+    })
+}
+
+pub(crate) fn dynamic_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
+    just(TokenKind::Keyword(KeywordKind::Dynamic))
+        .ignore_then(dynamic_value().delimited_by(
+            punct_token(PunctuationKind::OpenParen),
+            punct_token(PunctuationKind::CloseParen),
+        ))
+        .map(LitExprKind::Dynamic)
+}
+
+fn dynamic_value<'a, I: TokenInput<'a>>() -> parser_return!(DynamicValue) {
+    recursive(|value| {
+        let null = just(TokenKind::Keyword(KeywordKind::Null)).to(DynamicValue::Null);
+
+        let number = long_lit()
+            .or(real_lit())
+            .or(int_lit())
+            .or(decimal_lit())
+            .or(timespan_lit());
+        let negative_number = punct_token(PunctuationKind::Minus)
+            .ignore_then(number.clone().map_with(|lit, e| {
+                let span: Range<usize> = e.span();
+                (lit, span)
+            }))
+            .validate(|(lit, span), _, emitter| {
+                negate_lit(lit.clone()).unwrap_or_else(|| {
+                    emitter.emit(Rich::custom(
+                        span,
+                        "Failed to parse dynamic literal: value is out of range",
+                    ));
+                    lit // Return a default value
+                })
+            });
+        let scalar = boolean_lit()
+            .or(datetime_lit())
+            .or(guid_lit())
+            .or(string_lit())
+            .or(number)
+            .or(negative_number)
+            .map(|lit| DynamicValue::Scalar(Box::new(lit)));
+
+        let comma = punct_token(PunctuationKind::Comma).labelled("','");
+        let array = value
+            .clone()
+            .separated_by(comma.clone())
+            .collect()
+            .delimited_by(
+                punct_token(PunctuationKind::OpenBracket),
+                punct_token(PunctuationKind::CloseBracket).labelled("']'"),
+            )
+            .map(DynamicValue::Array);
+
+        let property = string_lit()
+            .map_with(|key, e| {
+                let span: Range<usize> = e.span();
+                match key {
+                    LitExprKind::String { value, .. } => (value, span),
+                    _ => unreachable!("string_lit only produces strings"),
+                }
+            })
+            .labelled("property name")
+            .then_ignore(punct_token(PunctuationKind::Colon).labelled("':'"))
+            .then(value);
+        let object = property
+            .separated_by(comma)
+            .collect::<Vec<_>>()
+            .delimited_by(
+                punct_token(PunctuationKind::OpenBrace),
+                punct_token(PunctuationKind::CloseBrace).labelled("'}'"),
+            )
+            .validate(|properties, _, emitter| {
+                let mut bag: Vec<(String, DynamicValue)> = Vec::with_capacity(properties.len());
+
+                for ((key, span), value) in properties {
+                    if bag.iter().any(|(existing, _)| *existing == key) {
+                        emitter.emit(Rich::custom(
+                            span,
+                            format!("Duplicate property '{key}' in dynamic object"),
+                        ));
+                        continue;
+                    }
+                    bag.push((key, value));
+                }
+
+                DynamicValue::Object(bag)
+            });
+
+        null.or(scalar)
+            .or(array)
+            .or(object)
+            .labelled("dynamic value")
+    })
+}
+
+fn negate_lit(lit: LitExprKind) -> Option<LitExprKind> {
+    Some(match lit {
+        LitExprKind::Long(value) => LitExprKind::Long(value.checked_neg()?),
+        LitExprKind::Real(value) => LitExprKind::Real(-value),
+        LitExprKind::Int(Some(value)) => LitExprKind::Int(Some(value.checked_neg()?)),
+        LitExprKind::Timespan(Some(value)) => LitExprKind::Timespan(Some(value.checked_neg()?)),
+        LitExprKind::Decimal(value) => LitExprKind::Decimal(value.map(|value| DecimalValue {
+            is_negative: !value.is_negative && value.digits != "0",
+            ..value
+        })),
+        lit => lit,
+    })
+}
+
+pub(crate) fn guid_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
+    select(|token, _| match token {
+        TokenKind::Literal(LiteralKind::Guid(value)) => Some((value, false)),
+        TokenKind::Literal(LiteralKind::RawGuid(value)) => Some((value, true)),
+        _ => None,
+    })
+    .validate(|(value, is_raw), e, emitter| {
+        let (offset, text) = if is_raw {
+            (0, &*value)
+        } else {
+            get_goo_value(&value)
+        };
+
+        match parse_lit_value(text, e.span(), offset, "guid", parse_guid) {
+            Ok(val) => LitExprKind::Guid(val),
+            Err(err) => {
+                emitter.emit(err);
+                LitExprKind::Guid(None) // Return a default value
+            }
+        }
+    })
+}
+
+pub(crate) fn int_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
+    select(|token, _| match token {
+        TokenKind::Literal(LiteralKind::Int(value)) => Some(value),
+        _ => None,
+    })
+    .validate(|value, e, emitter| {
+        let (offset, text) = get_goo_value(&value);
+        let parse = |text: &str| {
+            text.parse::<i32>().map_err(|err| ValueError {
+                span: 0..text.len(),
+                message: err.to_string(),
+            })
+        };
+
+        match parse_lit_value(text, e.span(), offset, "int", parse) {
+            Ok(val) => LitExprKind::Int(val),
+            Err(err) => {
+                emitter.emit(err);
+                LitExprKind::Int(None) // Return a default value
+            }
+        }
+    })
+}
+
+pub(crate) fn long_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
     select(|token, _| match token {
         TokenKind::Literal(LiteralKind::Long(value)) => Some(value),
         _ => None,
@@ -46,7 +236,7 @@ pub(crate) fn long_lit<'a>() -> parser_return!(LitExprKind) {
     })
 }
 
-pub(crate) fn real_lit<'a>() -> parser_return!(LitExprKind) {
+pub(crate) fn real_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
     select(|token, _| match token {
         TokenKind::Literal(LiteralKind::Real(value)) => Some(value),
         _ => None,
@@ -65,14 +255,177 @@ pub(crate) fn real_lit<'a>() -> parser_return!(LitExprKind) {
     })
 }
 
-// TODO: Handle escape sequences in string literals
-pub(crate) fn string_lit<'a>() -> parser_return!(LitExprKind) {
+pub(crate) fn timespan_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
+    select(|token, _| match token {
+        TokenKind::Literal(LiteralKind::Timespan(value)) => Some(value),
+        _ => None,
+    })
+    .validate(|value, e, emitter| {
+        let (offset, text) = if value.starts_with(|c: char| c.is_ascii_digit()) {
+            (0, &*value)
+        } else {
+            get_goo_value(&value)
+        };
+
+        match parse_lit_value(text, e.span(), offset, "timespan", parse_timespan) {
+            Ok(val) => LitExprKind::Timespan(val),
+            Err(err) => {
+                emitter.emit(err);
+                LitExprKind::Timespan(None) // Return a default value
+            }
+        }
+    })
+}
+
+pub(crate) fn string_lit<'a, I: TokenInput<'a>>() -> parser_return!(LitExprKind) {
     select(|token, _| match token {
         TokenKind::Literal(LiteralKind::String(value)) => Some(value),
         _ => None,
     })
+    .labelled("string literal")
+    .validate(|value, e, emitter| {
+        match decode_string_literal(&value) {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                emitter.emit(Rich::custom(
+                    e.span(),
+                    format!("Failed to parse string literal: {}", err),
+                ));
+                (String::new(), false) // Return a default value
+            }
+        }
+    })
     .repeated()
     .at_least(1)
     .collect()
-    .map(|tokens: Vec<String>| LitExprKind::String(tokens.concat()))
+    .map(|parts: Vec<_>| {
+        let is_obfuscated = parts.iter().any(|(_, is_obfuscated)| *is_obfuscated);
+        let value = parts.into_iter().map(|(value, _)| value).collect();
+        LitExprKind::String {
+            value,
+            is_obfuscated,
+        }
+    })
+}
+
+fn decode_string_literal(text: &str) -> Result<(String, bool), String> {
+    let (text, is_obfuscated) = match text.strip_prefix(['h', 'H']) {
+        Some(rest) => (rest, true),
+        None => (text, false),
+    };
+
+    if let Some(rest) = text.strip_prefix('@') {
+        let quote = &rest[..1];
+        let content = &rest[1..rest.len() - 1];
+        return Ok((content.replace(&quote.repeat(2), quote), is_obfuscated));
+    }
+
+    // Multi-line strings (```...``` or ~~~...~~~) take their content as is
+    if text.starts_with(['`', '~']) {
+        return Ok((text[3..text.len() - 3].to_string(), is_obfuscated));
+    }
+
+    let content = &text[1..text.len() - 1];
+    let mut value = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(index) = rest.find('\\') {
+        value.push_str(&rest[..index]);
+        let (c, len) = decode_escape(&rest[index + 1..])?;
+        value.push(c);
+        rest = &rest[index + 1 + len..];
+    }
+    value.push_str(rest);
+
+    Ok((value, is_obfuscated))
+}
+
+/// Decodes the escape sequence following a backslash, returning the character and the number of
+/// bytes consumed after the backslash.
+fn decode_escape(escape: &str) -> Result<(char, usize), String> {
+    let hex = |range: std::ops::Range<usize>| {
+        escape
+            .get(range)
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+    };
+
+    let (code, len) = match escape.as_bytes().first() {
+        Some(b'\\') => return Ok(('\\', 1)),
+        Some(b'\'') => return Ok(('\'', 1)),
+        Some(b'"') => return Ok(('"', 1)),
+        Some(b'a') => return Ok(('\u{07}', 1)),
+        Some(b'b') => return Ok(('\u{08}', 1)),
+        Some(b'f') => return Ok(('\u{0C}', 1)),
+        Some(b'n') => return Ok(('\n', 1)),
+        Some(b'r') => return Ok(('\r', 1)),
+        Some(b't') => return Ok(('\t', 1)),
+        Some(b'v') => return Ok(('\u{0B}', 1)),
+        Some(b'u') => match (hex(1..5), escape.get(5..7), hex(7..11)) {
+            // UTF-16 surrogate pair written as two escapes
+            (Some(high @ 0xD800..=0xDBFF), Some("\\u"), Some(low @ 0xDC00..=0xDFFF)) => {
+                (0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00), 11)
+            }
+            (Some(code), _, _) => (code, 5),
+            _ => return Err("expected 4 hex digits after '\\u'".to_string()),
+        },
+        Some(b'U') => (hex(1..9).ok_or("expected 8 hex digits after '\\U'")?, 9),
+        Some(b'x') => (hex(1..3).ok_or("expected 2 hex digits after '\\x'")?, 3),
+        Some(b'0'..=b'7') => {
+            let len = escape
+                .bytes()
+                .take(3)
+                .take_while(|b| (b'0'..=b'7').contains(b))
+                .count();
+            (
+                u32::from_str_radix(&escape[..len], 8).unwrap_or_default(),
+                len,
+            )
+        }
+        _ => {
+            return Err(format!(
+                "invalid escape sequence '\\{}'",
+                escape.chars().next().unwrap_or_default()
+            ));
+        }
+    };
+
+    char::from_u32(code)
+        .map(|c| (c, len))
+        .ok_or_else(|| format!("'\\{}' is not a valid character", &escape[..len]))
+}
+
+/// Parses a literal value, `None` for `null`, reporting errors at their position in the token
+/// spanning `span` where the value starts `offset` bytes in.
+fn parse_lit_value<'a, T>(
+    text: &str,
+    span: Range<usize>,
+    offset: usize,
+    kind: &str,
+    parse: impl Fn(&str) -> Result<T, ValueError>,
+) -> Result<Option<T>, Rich<'a, TokenKind<'a>, Range<usize>>> {
+    if text == "null" {
+        return Ok(None);
+    }
+
+    parse(text).map(Some).map_err(|err| {
+        let start = span.start + offset;
+        Rich::custom(
+            start + err.span.start..start + err.span.end,
+            format!("Failed to parse {kind} literal: {}", err.message),
+        )
+    })
+}
+
+/// Gets the trimmed value inside the parentheses of a goo literal like `datetime( 2024-01-02 )`,
+/// along with its byte offset in the token text.
+fn get_goo_value(text: &str) -> (usize, &str) {
+    let start = text.find('(').map_or(0, |index| index + 1);
+    let end = text
+        .rfind(')')
+        .filter(|&end| end >= start)
+        .unwrap_or(text.len());
+    let value = &text[start..end];
+    let trimmed = value.trim_start();
+
+    (start + value.len() - trimmed.len(), trimmed.trim_end())
 }
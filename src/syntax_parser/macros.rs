@@ -1,6 +1,13 @@
 #[macro_export]
 macro_rules! parser_return {
     ($output:ty) => {
-        impl Parser<'a, &'a [TokenKind], $output, extra::Err<Rich<'a, TokenKind>>> + Clone
+        impl Parser<'a, I, $output, extra::Err<Rich<'a, TokenKind<'a>, std::ops::Range<usize>>>> + Clone
+    };
+}
+
+#[macro_export]
+macro_rules! parser_arg {
+    ($output:ty) => {
+        impl Parser<'a, I, $output, extra::Err<Rich<'a, TokenKind<'a>, std::ops::Range<usize>>>> + Clone + 'a
     };
 }
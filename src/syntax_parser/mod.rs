@@ -2,12 +2,15 @@ mod constants;
 mod expr_parsers;
 mod lit_parsers;
 mod macros;
+mod operator_parsers;
 mod query_parser;
 mod types;
 mod utilities;
+mod value_parsers;
 
 #[cfg(test)]
 mod tests;
 
 pub use query_parser::query;
 pub use types::*;
+pub use utilities::{TokenInput, token_input, token_stream};
@@ -0,0 +1,291 @@
+use crate::{
+    parser_arg, parser_return,
+    syntax_parser::{
+        constants::{HINTS, JOIN_HINTS, JOIN_KINDS, SUMMARIZE_HINTS},
+        expr_parsers::*,
+        types::*,
+        utilities::*,
+    },
+    token_parser::{KeywordKind, PunctuationKind, TokenKind},
+};
+use chumsky::{prelude::*, primitive::select};
+use std::ops::Range;
+
+pub(crate) fn query_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    take_operator(expr.clone())
+        .or(where_operator(expr.clone()))
+        .or(extend_operator(expr.clone()))
+        .or(summarize_operator(expr.clone()))
+        .or(join_operator(expr.clone()))
+        .or(project_operator(expr))
+        .boxed()
+}
+
+pub(crate) fn take_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    just(TokenKind::Keyword(KeywordKind::Take))
+        .or(just(TokenKind::Keyword(KeywordKind::Limit)))
+        .ignore_then(expr)
+        .map(|count| QueryOperator::Take {
+            count: Box::new(count),
+        })
+}
+
+pub(crate) fn where_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    let keyword = just(TokenKind::Keyword(KeywordKind::Where))
+        .to("where")
+        .or(just(TokenKind::Keyword(KeywordKind::Filter)).to("filter"));
+
+    keyword
+        .map_with(|keyword, e| {
+            let span: Range<usize> = e.span();
+            (keyword, span)
+        })
+        .then(expr.map(Some).or(stage_end().rewind().to(None)))
+        .validate(|((keyword, span), predicate), _, emitter| {
+            let predicate = predicate.unwrap_or_else(|| {
+                emitter.emit(Rich::custom(
+                    span,
+                    format!("Expected a predicate after '{keyword}'"),
+                ));
+                // Return a default value
+                ExprKind::Literal(LitExprKind::Boolean(true))
+            });
+
+            QueryOperator::Where {
+                predicate: Box::new(predicate),
+            }
+        })
+}
+
+pub(crate) fn extend_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    just(TokenKind::Keyword(KeywordKind::Extend))
+        .ignore_then(
+            named_expr(expr.clone())
+                .or(expr)
+                .separated_by(punct_token(PunctuationKind::Comma))
+                .at_least(1)
+                .collect::<Vec<_>>(),
+        )
+        .map(|exprs| QueryOperator::Extend { exprs })
+}
+
+pub(crate) fn summarize_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    let aggregates = just(TokenKind::Keyword(KeywordKind::By))
+        .not()
+        .ignore_then(named_expr(expr.clone()).or(expr.clone()))
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .collect::<Vec<_>>();
+    let by = just(TokenKind::Keyword(KeywordKind::By)).ignore_then(
+        named_expr(expr.clone())
+            .or(expr)
+            .separated_by(punct_token(PunctuationKind::Comma))
+            .at_least(1)
+            .collect::<Vec<_>>(),
+    );
+
+    just(TokenKind::Keyword(KeywordKind::Summarize))
+        .map_with(|_, e| {
+            let span: Range<usize> = e.span();
+            span
+        })
+        .then(
+            hint(SUMMARIZE_HINTS, "summarize")
+                .repeated()
+                .collect::<Vec<_>>(),
+        )
+        .then(aggregates)
+        .then(by.or_not())
+        .validate(|(((span, hints), aggregates), by), _, emitter| {
+            if aggregates.is_empty() && by.is_none() {
+                emitter.emit(Rich::custom(
+                    span,
+                    "Expected an aggregate or 'by' clause after 'summarize'",
+                ));
+            }
+
+            QueryOperator::Summarize {
+                hints,
+                aggregates,
+                by: by.unwrap_or_default(),
+            }
+        })
+}
+
+pub(crate) fn join_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    let keyword = just(TokenKind::Keyword(KeywordKind::Join))
+        .to(false)
+        .or(just(TokenKind::Keyword(KeywordKind::Lookup)).to(true));
+    let hints = hint(JOIN_HINTS, "join").repeated().collect::<Vec<_>>();
+    let on = just(TokenKind::Keyword(KeywordKind::On)).ignore_then(
+        expr.clone()
+            .separated_by(punct_token(PunctuationKind::Comma))
+            .at_least(1)
+            .collect::<Vec<_>>(),
+    );
+
+    keyword
+        .then(hints.clone())
+        .then(join_kind().or_not())
+        .then(hints)
+        .then(expr)
+        .then(on)
+        .validate(
+            |(((((is_lookup, mut hints), kind), more_hints), right), on), _, emitter| {
+                hints.extend(more_hints);
+                let right = Box::new(right);
+                let kind = kind.map(|(kind, span)| {
+                    if is_lookup && !matches!(kind, JoinKind::Inner | JoinKind::LeftOuter) {
+                        emitter.emit(Rich::custom(
+                            span,
+                            "Unsupported lookup kind, expected 'leftouter' or 'inner'",
+                        ));
+                    }
+                    kind
+                });
+
+                if is_lookup {
+                    QueryOperator::Lookup {
+                        kind,
+                        hints,
+                        right,
+                        on,
+                    }
+                } else {
+                    QueryOperator::Join {
+                        kind,
+                        hints,
+                        right,
+                        on,
+                    }
+                }
+            },
+        )
+}
+
+pub(crate) fn join_kind<'a, I: TokenInput<'a>>() -> parser_return!((JoinKind, Range<usize>)) {
+    just(TokenKind::Keyword(KeywordKind::Kind))
+        .ignore_then(punct_token(PunctuationKind::Equal))
+        .ignore_then(iden_name().map_with(|name, e| {
+            let span: Range<usize> = e.span();
+            (name, span)
+        }))
+        .validate(|(name, span), _, emitter| {
+            let kind = match JOIN_KINDS.iter().find(|kind| kind.0 == name) {
+                Some(kind) => kind.1.clone(),
+                None => {
+                    emitter.emit(Rich::custom(
+                        span.clone(),
+                        format!("Unknown join kind '{name}'"),
+                    ));
+                    // Return a default value
+                    JoinKind::InnerUnique
+                }
+            };
+
+            (kind, span)
+        })
+}
+
+pub(crate) fn project_operator<'a, I: TokenInput<'a>>(
+    expr: parser_arg!(ExprKind),
+) -> parser_return!(QueryOperator) {
+    let exprs = named_expr(expr.clone())
+        .or(expr)
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .at_least(1)
+        .collect::<Vec<_>>();
+    let columns = column_name_expr()
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .at_least(1)
+        .collect::<Vec<_>>();
+    let renames = named_expr(ext_name().map(ExprKind::Name))
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .at_least(1)
+        .collect::<Vec<_>>();
+    let sort_order = just(TokenKind::Keyword(KeywordKind::Asc))
+        .to(SortOrder::Asc)
+        .or(just(TokenKind::Keyword(KeywordKind::Desc)).to(SortOrder::Desc))
+        .or(just(TokenKind::Keyword(KeywordKind::GrannyAsc)).to(SortOrder::GrannyAsc))
+        .or(just(TokenKind::Keyword(KeywordKind::GrannyDesc)).to(SortOrder::GrannyDesc));
+    let reorder_columns = column_name_expr()
+        .then(sort_order.or_not())
+        .separated_by(punct_token(PunctuationKind::Comma))
+        .at_least(1)
+        .collect::<Vec<_>>();
+
+    let project = just(TokenKind::Keyword(KeywordKind::Project))
+        .ignore_then(exprs.clone())
+        .map(|exprs| QueryOperator::Project { exprs });
+    let project_away = just(TokenKind::Keyword(KeywordKind::ProjectAway))
+        .ignore_then(columns.clone())
+        .map(|columns| QueryOperator::ProjectAway { columns });
+    let project_keep = just(TokenKind::Keyword(KeywordKind::ProjectKeep))
+        .ignore_then(columns.clone())
+        .map(|columns| QueryOperator::ProjectKeep { columns });
+    let project_rename = just(TokenKind::Keyword(KeywordKind::ProjectRename))
+        .ignore_then(renames)
+        .map(|renames| QueryOperator::ProjectRename { renames });
+    let project_reorder = just(TokenKind::Keyword(KeywordKind::ProjectReorder))
+        .ignore_then(reorder_columns)
+        .map(|columns| QueryOperator::ProjectReorder { columns });
+    let project_smart = just(TokenKind::Keyword(KeywordKind::ProjectSmart))
+        .ignore_then(exprs)
+        .map(|exprs| QueryOperator::ProjectSmart { exprs });
+    let project_by_names = just(TokenKind::Keyword(KeywordKind::ProjectByNames))
+        .ignore_then(columns)
+        .map(|columns| QueryOperator::ProjectByNames { columns });
+
+    project
+        .or(project_away)
+        .or(project_keep)
+        .or(project_rename)
+        .or(project_reorder)
+        .or(project_smart)
+        .or(project_by_names)
+        .boxed()
+}
+
+pub(crate) fn hint<'a, I: TokenInput<'a>>(
+    allowed: &'static [HintKind],
+    operator: &'static str,
+) -> parser_return!((HintKind, ExprKind)) {
+    let kind = select(|token, _| match token {
+        TokenKind::Keyword(value) => HINTS.iter().find(|hint| value == hint.0),
+        _ => None,
+    })
+    .validate(move |(_, name, kind), e, emitter| {
+        if !allowed.contains(kind) {
+            emitter.emit(Rich::custom(
+                e.span(),
+                format!("Unsupported hint '{name}' for '{operator}'"),
+            ));
+        }
+        kind.clone()
+    });
+    let value = lit_expr()
+        .or(ext_name().map(ExprKind::Name))
+        .or(kw_as_iden_name().map(ExprKind::Name));
+
+    kind.then_ignore(punct_token(PunctuationKind::Equal))
+        .then(value)
+}
+
+fn stage_end<'a, I: TokenInput<'a>>() -> parser_return!(()) {
+    punct_token(PunctuationKind::Bar)
+        .ignored()
+        .or(punct_token(PunctuationKind::CloseParen).ignored())
+        .or(just(TokenKind::EndOfFile).ignored())
+        .or(end())
+}
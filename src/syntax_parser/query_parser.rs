@@ -1,13 +1,13 @@
 use crate::{
     parser_return,
-    syntax_parser::{SyntaxKind, expr_parsers::*},
+    syntax_parser::{SyntaxKind, TokenInput, expr_parsers::*},
     token_parser::TokenKind,
 };
 use chumsky::prelude::*;
 
-pub fn query<'a>() -> parser_return!(SyntaxKind) {
-    named_expr()
-        .or(unnamed_expr())
+pub fn query<'a, I: TokenInput<'a>>() -> parser_return!(SyntaxKind) {
+    named_expr(unnamed_expr())
+        .or(pipe_expr())
         .then_ignore(just(TokenKind::EndOfFile).or_not())
         .map(SyntaxKind::Expr)
 }
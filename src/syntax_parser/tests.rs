@@ -1,8 +1,11 @@
-use super::{expr_parsers::*, lit_parsers::*, types::*};
-use crate::token_parser::{ParseOptions, TokenKind, parse_tokens};
-use chumsky::prelude::*;
+use super::{expr_parsers::*, lit_parsers::*, query, token_input, token_stream, types::*};
+use crate::token_parser::{ParseOptions, Token, parse_tokens};
+use chumsky::{
+    error::{RichPattern, RichReason},
+    prelude::*,
+};
 
-fn parse_tokens_no_eof(input: &str) -> Vec<TokenKind> {
+fn parse_tokens_no_eof(input: &str) -> Vec<Token<'_>> {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     parse_tokens(input, &options)
 }
@@ -10,7 +13,7 @@ fn parse_tokens_no_eof(input: &str) -> Vec<TokenKind> {
 #[test]
 fn test_additive_multiplicative_precedence() {
     let tokens = parse_tokens_no_eof("1 + 2 * 3");
-    let result = additive_expr().parse(&tokens);
+    let result = additive_expr(unnamed_expr()).parse(token_input(&tokens));
     assert!(!result.has_errors());
     let expr = result.into_output().expect("expected additive expr");
 
@@ -31,7 +34,7 @@ fn test_additive_multiplicative_precedence() {
 #[test]
 fn test_relational_before_equality() {
     let tokens = parse_tokens_no_eof("1 < 2 == 3");
-    let result = equality_expr().parse(&tokens);
+    let result = equality_expr(unnamed_expr()).parse(token_input(&tokens));
     assert!(!result.has_errors());
     let expr = result.into_output().expect("expected equality expr");
 
@@ -52,7 +55,7 @@ fn test_relational_before_equality() {
 #[test]
 fn test_logical_and_before_or() {
     let tokens = parse_tokens_no_eof("true and false or true");
-    let result = logical_or_expr().parse(&tokens);
+    let result = logical_or_expr(unnamed_expr()).parse(token_input(&tokens));
     assert!(!result.has_errors());
     let expr = result.into_output().expect("expected logical or expr");
 
@@ -80,7 +83,7 @@ fn test_lit_expr_variants() {
 
     for (input, expected) in cases {
         let tokens = parse_tokens_no_eof(input);
-        let result = lit_expr().parse(&tokens);
+        let result = lit_expr().parse(token_input(&tokens));
         assert!(!result.has_errors(), "input: {input}");
         let expr = result.into_output().expect("expected literal expr");
         assert_eq!(expr, expected, "input: {input}");
@@ -90,7 +93,7 @@ fn test_lit_expr_variants() {
 #[test]
 fn test_boolean_lit_parser() {
     let tokens = parse_tokens_no_eof("false");
-    let result = boolean_lit().parse(&tokens);
+    let result = boolean_lit().parse(token_input(&tokens));
     assert!(!result.has_errors());
     let lit = result.into_output().expect("expected boolean literal");
     assert_eq!(lit, LitExprKind::Boolean(false));
@@ -99,7 +102,7 @@ fn test_boolean_lit_parser() {
 #[test]
 fn test_long_lit_parser() {
     let tokens = parse_tokens_no_eof("42");
-    let result = long_lit().parse(&tokens);
+    let result = long_lit().parse(token_input(&tokens));
     assert!(!result.has_errors());
     let lit = result.into_output().expect("expected long literal");
     assert_eq!(lit, LitExprKind::Long(42));
@@ -108,7 +111,7 @@ fn test_long_lit_parser() {
 #[test]
 fn test_real_lit_parser() {
     let tokens = parse_tokens_no_eof("3.25");
-    let result = real_lit().parse(&tokens);
+    let result = real_lit().parse(token_input(&tokens));
     assert!(!result.has_errors());
     let lit = result.into_output().expect("expected real literal");
     assert_eq!(lit, LitExprKind::Real(3.25));
@@ -117,19 +120,592 @@ fn test_real_lit_parser() {
 #[test]
 fn test_string_lit_parser() {
     let tokens = parse_tokens_no_eof("'hello'");
-    let result = string_lit().parse(&tokens);
+    let result = string_lit().parse(token_input(&tokens));
     assert!(!result.has_errors());
     let lit = result.into_output().expect("expected string literal");
-    assert_eq!(lit, LitExprKind::String("'hello'".to_string()));
+    assert_eq!(
+        lit,
+        LitExprKind::String {
+            value: "hello".to_string(),
+            is_obfuscated: false,
+        }
+    );
 }
 
 #[test]
 fn test_string_lit_concat_parser() {
     let tokens = parse_tokens_no_eof("'a' \"b\"");
-    let result = string_lit().parse(&tokens);
+    let result = string_lit().parse(token_input(&tokens));
     assert!(!result.has_errors());
-    let lit = result.into_output().expect("expected concatenated string literal");
-    assert_eq!(lit, LitExprKind::String("'a'\"b\"".to_string()));
+    let lit = result
+        .into_output()
+        .expect("expected concatenated string literal");
+    assert_eq!(
+        lit,
+        LitExprKind::String {
+            value: "ab".to_string(),
+            is_obfuscated: false,
+        }
+    );
+}
+
+#[test]
+fn test_string_lit_decoding() {
+    let cases = [
+        (r"'a\tb\nc'", "a\tb\nc", false),
+        (
+            r#""\"quoted\" \\ 'single'""#,
+            "\"quoted\" \\ 'single'",
+            false,
+        ),
+        (r"'\a\b\f\v\r'", "\u{07}\u{08}\u{0C}\u{0B}\r", false),
+        (r"'\u00e9\U0001F600\x41'", "é😀A", false),
+        (r"'\uD83D\uDE00'", "😀", false),
+        (r"'\101\60\0'", "A0\0", false),
+        (r"@'C:\path\''s'", r"C:\path\'s", false),
+        (r#"@"say ""hi""""#, "say \"hi\"", false),
+        ("```line 1\nline 2```", "line 1\nline 2", false),
+        ("~~~a'b\"c~~~", "a'b\"c", false),
+        (r"h'secret\n'", "secret\n", true),
+        (r"H@'secret\n'", r"secret\n", true),
+        (r"'a' h'b' 'c'", "abc", true),
+    ];
+
+    for (input, expected, is_obfuscated) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = string_lit().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(LitExprKind::String {
+                value: expected.to_string(),
+                is_obfuscated,
+            }),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_string_lit_invalid_code_point() {
+    let tokens = parse_tokens_no_eof(r"'ok' '\uD800'");
+    let result = string_lit().parse(token_input(&tokens));
+    let errors = result.into_errors();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(5..13));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg)
+            if msg == r"Failed to parse string literal: '\uD800' is not a valid character"
+    ));
+}
+
+#[test]
+fn test_datetime_lit_parser() {
+    let cases = [
+        ("datetime(2024-01-02)", Some(638397504000000000)),
+        ("date(2024-01-02)", Some(638397504000000000)),
+        (
+            "datetime(2014-05-25T08:20:03.1234567Z)",
+            Some(635366028031234567),
+        ),
+        (
+            "datetime(2014-05-25 08:20:03.1234567)",
+            Some(635366028031234567),
+        ),
+        (
+            "datetime(2014-05-25T08:20:03+02:00)",
+            Some(635365956030000000),
+        ),
+        (
+            "datetime(Sat, 8 Nov 14 15:05:02 GMT)",
+            Some(635510559020000000),
+        ),
+        ("datetime(8 Nov 14 15:05:02)", Some(635510559020000000)),
+        (
+            "datetime(Saturday, 08-Nov-14 15:05:02 GMT)",
+            Some(635510559020000000),
+        ),
+        ("datetime(08-Nov-14 15:05)", Some(635510559000000000)),
+        ("datetime(31-Dec-99 23:00)", Some(630822780000000000)),
+        (
+            "datetime( 9999-12-31 23:59:59.9999999 )",
+            Some(3155378975999999999),
+        ),
+        ("datetime(null)", None),
+    ];
+
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = lit_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::Literal(LitExprKind::DateTime(expected))),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_datetime_lit_errors() {
+    let cases = [
+        ("datetime(2024-13-01)", 14..16, "month 13 is out of range"),
+        (
+            "datetime(2023-02-29)",
+            17..19,
+            "day 29 is out of range for 2023-02",
+        ),
+        (
+            "datetime(2024-01-02T25:00)",
+            20..22,
+            "hour 25 is out of range",
+        ),
+        ("datetime(2024-1-02)", 14..15, "expected 2 digit month"),
+        (
+            "datetime(2024-01-02x)",
+            19..20,
+            "expected end of datetime, found 'x'",
+        ),
+        (
+            "datetime(8 Foo 14 15:05)",
+            11..14,
+            "unknown month name 'Foo'",
+        ),
+        (
+            "datetime(8 Nov 14 15:05 PST)",
+            24..27,
+            "unknown time zone 'PST'",
+        ),
+        (
+            "datetime(Sun, 8 Nov 14 15:05)",
+            9..12,
+            "day of week 'Sun' does not match the date, which is a Saturday",
+        ),
+        (
+            "datetime(2024-01-02 10:00:00.12345678)",
+            29..37,
+            "fraction of a second has more than 7 digits",
+        ),
+    ];
+
+    for (input, span, message) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let errors = datetime_lit().parse(token_input(&tokens)).into_errors();
+        assert_eq!(errors.len(), 1, "input: {input}");
+        assert_eq!(errors[0].span(), &span, "input: {input}");
+        assert!(
+            matches!(
+                errors[0].reason(),
+                RichReason::Custom(msg)
+                    if *msg == format!("Failed to parse datetime literal: {message}")
+            ),
+            "input: {input}, reason: {:?}",
+            errors[0].reason()
+        );
+    }
+}
+
+#[test]
+fn test_timespan_lit_suffixes() {
+    let units = [
+        (&["d", "day", "days"][..], 864_000_000_000),
+        (&["h", "hr", "hrs", "hour", "hours"], 36_000_000_000),
+        (&["m", "min", "minute", "minutes"], 600_000_000),
+        (&["s", "sec", "second", "seconds"], 10_000_000),
+        (
+            &[
+                "ms",
+                "milli",
+                "millis",
+                "millisec",
+                "millisecond",
+                "milliseconds",
+            ],
+            10_000,
+        ),
+        (
+            &["micro", "micros", "microsec", "microsecond", "microseconds"],
+            10,
+        ),
+        (&["tick", "ticks"], 1),
+    ];
+
+    for (suffixes, ticks) in units {
+        for suffix in suffixes {
+            let input = format!("3{suffix}");
+            let tokens = parse_tokens_no_eof(&input);
+            let result = timespan_lit().parse(token_input(&tokens));
+            assert!(!result.has_errors(), "input: {input}");
+            assert_eq!(
+                result.into_output(),
+                Some(LitExprKind::Timespan(Some(3 * ticks))),
+                "input: {input}"
+            );
+        }
+    }
+
+    for suffix in ["nano", "nanos", "nanosec", "nanosecond", "nanoseconds"] {
+        let input = format!("300{suffix}");
+        let tokens = parse_tokens_no_eof(&input);
+        let result = timespan_lit().parse(token_input(&tokens));
+        assert_eq!(
+            result.into_output(),
+            Some(LitExprKind::Timespan(Some(3))),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_timespan_lit_parser() {
+    let cases = [
+        ("1.5h", Some(54_000_000_000)),
+        ("0.1s", Some(1_000_000)),
+        ("3.5days", Some(3_024_000_000_000)),
+        ("250nanoseconds", Some(2)),
+        ("time(2)", Some(1_728_000_000_000)),
+        ("time(15 seconds)", Some(150_000_000)),
+        ("time(1:30)", Some(54_000_000_000)),
+        ("time(0.12:34:56.7)", Some(452_967_000_000)),
+        ("timespan( 1.02:03:04 )", Some(937_840_000_000)),
+        ("time(-1.02:03:04)", Some(-937_840_000_000)),
+        ("time(-2h)", Some(-72_000_000_000)),
+        ("time(null)", None),
+    ];
+
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = lit_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::Literal(LitExprKind::Timespan(expected))),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_timespan_lit_errors() {
+    let cases = [
+        ("time(1.25:00)", 7..9, "hours 25 is out of range"),
+        ("time(1:60)", 7..9, "minutes 60 is out of range"),
+        (
+            "time(5 fortnights)",
+            7..17,
+            "unknown timespan unit 'fortnights'",
+        ),
+        (
+            "time(1:2:3x)",
+            10..11,
+            "expected end of timespan, found 'x'",
+        ),
+        (
+            "time(99999999999999999999d)",
+            5..26,
+            "timespan is out of range",
+        ),
+        ("time(20000000.00:00:00)", 5..22, "timespan is out of range"),
+        ("time(10675199.23:59:59)", 5..22, "timespan is out of range"),
+    ];
+
+    for (input, span, message) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let errors = timespan_lit().parse(token_input(&tokens)).into_errors();
+        assert_eq!(errors.len(), 1, "input: {input}");
+        assert_eq!(errors[0].span(), &span, "input: {input}");
+        assert!(
+            matches!(
+                errors[0].reason(),
+                RichReason::Custom(msg)
+                    if *msg == format!("Failed to parse timespan literal: {message}")
+            ),
+            "input: {input}, reason: {:?}",
+            errors[0].reason()
+        );
+    }
+}
+
+#[test]
+fn test_guid_lit_parser() {
+    let value = Some(0x12345678_9abc_def0_1234_56789abcdef0);
+    let cases = [
+        ("12345678-9abc-def0-1234-56789abcdef0", value),
+        ("guid(12345678-9ABC-DEF0-1234-56789ABCDEF0)", value),
+        ("guid({12345678-9abc-def0-1234-56789abcdef0})", value),
+        ("guid( 123456789abcdef0123456789abcdef0 )", value),
+        ("guid(null)", None),
+    ];
+
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = lit_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::Literal(LitExprKind::Guid(expected))),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_decimal_lit_parser() {
+    let decimal = |is_negative, digits: &str, scale| {
+        Some(DecimalValue {
+            is_negative,
+            digits: digits.to_string(),
+            scale,
+        })
+    };
+    let cases = [
+        ("decimal(1.50)", decimal(false, "150", 2)),
+        ("decimal(-0.05)", decimal(true, "5", 2)),
+        ("decimal(+7)", decimal(false, "7", 0)),
+        ("decimal(-0.0)", decimal(false, "0", 1)),
+        ("decimal(1e3)", decimal(false, "1000", 0)),
+        ("decimal(12.5E-3)", decimal(false, "125", 4)),
+        (
+            "decimal(123456789012345678901234567890.123)",
+            decimal(false, "123456789012345678901234567890123", 3),
+        ),
+        ("decimal(null)", None),
+    ];
+
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = lit_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::Literal(LitExprKind::Decimal(expected))),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_int_lit_parser() {
+    let cases = [
+        ("int(3)", Some(3)),
+        ("int( -3 )", Some(-3)),
+        ("int32(2147483647)", Some(i32::MAX)),
+        ("int(null)", None),
+    ];
+
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = lit_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::Literal(LitExprKind::Int(expected))),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_guid_decimal_int_lit_errors() {
+    let cases = [
+        (
+            "guid(1234)",
+            5..9,
+            "guid",
+            "expected 32 hex digits, optionally grouped as 8-4-4-4-12",
+        ),
+        (
+            "guid(1234567g-9abc-def0-1234-56789abcdef0)",
+            12..13,
+            "guid",
+            "expected hex digit, found 'g'",
+        ),
+        (
+            "guid(12345678-9abc-def0_1234-56789abcdef0)",
+            23..24,
+            "guid",
+            "expected '-', found '_'",
+        ),
+        (
+            "decimal(1.2.3)",
+            11..12,
+            "decimal",
+            "expected end of decimal, found '.'",
+        ),
+        (
+            "decimal(1e5000)",
+            10..14,
+            "decimal",
+            "exponent is larger than 1000",
+        ),
+        (
+            "int(3000000000)",
+            4..14,
+            "int",
+            "number too large to fit in target type",
+        ),
+    ];
+
+    for (input, span, kind, message) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let errors = lit_expr().parse(token_input(&tokens)).into_errors();
+        assert_eq!(errors.len(), 1, "input: {input}");
+        assert_eq!(errors[0].span(), &span, "input: {input}");
+        assert!(
+            matches!(
+                errors[0].reason(),
+                RichReason::Custom(msg)
+                    if *msg == format!("Failed to parse {kind} literal: {message}")
+            ),
+            "input: {input}, reason: {:?}",
+            errors[0].reason()
+        );
+    }
+}
+
+#[test]
+fn test_dynamic_lit_parser() {
+    let scalar = |lit| DynamicValue::Scalar(Box::new(lit));
+    let input = r#"dynamic({"a": 1, "b": [true, -2.5, null], "c": {"d": datetime(2024-01-02)},
+        "e": -1h, "f": guid(00000000-0000-0000-0000-000000000001), 'g': "x"})"#;
+    let tokens = parse_tokens_no_eof(input);
+    let result = lit_expr().parse(token_input(&tokens));
+    assert!(
+        !result.has_errors(),
+        "{:?}",
+        result.errors().collect::<Vec<_>>()
+    );
+
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::Literal(LitExprKind::Dynamic(
+            DynamicValue::Object(vec![
+                ("a".to_string(), scalar(LitExprKind::Long(1))),
+                (
+                    "b".to_string(),
+                    DynamicValue::Array(vec![
+                        scalar(LitExprKind::Boolean(true)),
+                        scalar(LitExprKind::Real(-2.5)),
+                        DynamicValue::Null,
+                    ])
+                ),
+                (
+                    "c".to_string(),
+                    DynamicValue::Object(vec![(
+                        "d".to_string(),
+                        scalar(LitExprKind::DateTime(Some(638397504000000000)))
+                    )])
+                ),
+                (
+                    "e".to_string(),
+                    scalar(LitExprKind::Timespan(Some(-36_000_000_000)))
+                ),
+                ("f".to_string(), scalar(LitExprKind::Guid(Some(1)))),
+                (
+                    "g".to_string(),
+                    scalar(LitExprKind::String {
+                        value: "x".to_string(),
+                        is_obfuscated: false,
+                    })
+                ),
+            ])
+        )))
+    );
+}
+
+#[test]
+fn test_dynamic_lit_simple_values() {
+    let cases = [
+        ("dynamic(null)", DynamicValue::Null),
+        ("dynamic([])", DynamicValue::Array(vec![])),
+        ("dynamic({})", DynamicValue::Object(vec![])),
+        (
+            "dynamic(-7)",
+            DynamicValue::Scalar(Box::new(LitExprKind::Long(-7))),
+        ),
+        (
+            "dynamic([[1], {}])",
+            DynamicValue::Array(vec![
+                DynamicValue::Array(vec![DynamicValue::Scalar(Box::new(LitExprKind::Long(1)))]),
+                DynamicValue::Object(vec![]),
+            ]),
+        ),
+    ];
+
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = dynamic_lit().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(LitExprKind::Dynamic(expected)),
+            "input: {input}"
+        );
+    }
+}
+
+#[test]
+fn test_dynamic_lit_errors() {
+    let cases = [
+        (r#"dynamic({"a" 1})"#, 13..14, "':'"),
+        (r#"dynamic({a: 1})"#, 9..10, "property name"),
+        (r#"dynamic({a: 1})"#, 9..10, "'}'"),
+        (r#"dynamic({"a": 1,})"#, 16..17, "property name"),
+        (r#"dynamic({"a": 1 "b": 2})"#, 16..19, "','"),
+        (r#"dynamic({"a": 1 "b": 2})"#, 16..19, "'}'"),
+        (r#"dynamic([1, 2)"#, 13..14, "','"),
+        (r#"dynamic([1, 2)"#, 13..14, "']'"),
+        (r#"dynamic({"a": })"#, 14..15, "dynamic value"),
+    ];
+
+    for (input, span, label) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let errors = dynamic_lit().parse(token_input(&tokens)).into_errors();
+        assert_eq!(errors.len(), 1, "input: {input}");
+        assert_eq!(errors[0].span(), &span, "input: {input}");
+        assert!(
+            errors[0]
+                .expected()
+                .any(|pattern| matches!(pattern, RichPattern::Label(l) if l == label)),
+            "input: {input}, expected: {:?}",
+            errors[0].expected().collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn test_dynamic_lit_negation_overflow() {
+    let cases = [
+        ("dynamic([-int(-2147483648)])", 10..26),
+        ("dynamic([-time(-10675199.02:48:05.4775808)])", 10..42),
+    ];
+
+    for (input, span) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let errors = dynamic_lit().parse(token_input(&tokens)).into_errors();
+        assert_eq!(errors.len(), 1, "input: {input}");
+        assert_eq!(errors[0].span(), &span, "input: {input}");
+        assert!(matches!(
+            errors[0].reason(),
+            RichReason::Custom(msg)
+                if msg == "Failed to parse dynamic literal: value is out of range"
+        ));
+    }
+}
+
+#[test]
+fn test_dynamic_lit_duplicate_property() {
+    let tokens = parse_tokens_no_eof(r#"dynamic({"a": 1, "b": 2, "a": 3})"#);
+    let errors = dynamic_lit().parse(token_input(&tokens)).into_errors();
+
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(25..28));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg) if msg == "Duplicate property 'a' in dynamic object"
+    ));
 }
 
 #[test]
@@ -157,7 +733,7 @@ fn test_multiplicative_ops() {
 
     for (input, op, left, right) in cases {
         let tokens = parse_tokens_no_eof(input);
-        let result = multiplicative_expr().parse(&tokens);
+        let result = multiplicative_expr(unnamed_expr()).parse(token_input(&tokens));
         assert!(!result.has_errors(), "input: {input}");
         let expr = result.into_output().expect("expected multiplicative expr");
         assert_eq!(
@@ -191,7 +767,7 @@ fn test_additive_ops() {
 
     for (input, op, left, right) in cases {
         let tokens = parse_tokens_no_eof(input);
-        let result = additive_expr().parse(&tokens);
+        let result = additive_expr(unnamed_expr()).parse(token_input(&tokens));
         assert!(!result.has_errors(), "input: {input}");
         let expr = result.into_output().expect("expected additive expr");
         assert_eq!(
@@ -217,7 +793,7 @@ fn test_relational_ops() {
 
     for (input, op) in cases {
         let tokens = parse_tokens_no_eof(input);
-        let result = relational_expr().parse(&tokens);
+        let result = relational_expr(unnamed_expr()).parse(token_input(&tokens));
         assert!(!result.has_errors(), "input: {input}");
         let expr = result.into_output().expect("expected relational expr");
         assert_eq!(
@@ -242,7 +818,7 @@ fn test_equality_ops() {
 
     for (input, op) in cases {
         let tokens = parse_tokens_no_eof(input);
-        let result = equality_expr().parse(&tokens);
+        let result = equality_expr(unnamed_expr()).parse(token_input(&tokens));
         assert!(!result.has_errors(), "input: {input}");
         let expr = result.into_output().expect("expected equality expr");
         assert_eq!(
@@ -260,7 +836,7 @@ fn test_equality_ops() {
 #[test]
 fn test_logical_and_expr() {
     let tokens = parse_tokens_no_eof("true and false");
-    let result = logical_and_expr().parse(&tokens);
+    let result = logical_and_expr(unnamed_expr()).parse(token_input(&tokens));
     assert!(!result.has_errors());
     let expr = result.into_output().expect("expected logical and expr");
 
@@ -277,7 +853,7 @@ fn test_logical_and_expr() {
 #[test]
 fn test_logical_or_expr() {
     let tokens = parse_tokens_no_eof("true or false");
-    let result = logical_or_expr().parse(&tokens);
+    let result = logical_or_expr(unnamed_expr()).parse(token_input(&tokens));
     assert!(!result.has_errors());
     let expr = result.into_output().expect("expected logical or expr");
 
@@ -294,7 +870,7 @@ fn test_logical_or_expr() {
 #[test]
 fn test_unnamed_expr_entry() {
     let tokens = parse_tokens_no_eof("1 + 2");
-    let result = unnamed_expr().parse(&tokens);
+    let result = unnamed_expr().parse(token_input(&tokens));
     assert!(!result.has_errors());
     let expr = result.into_output().expect("expected unnamed expr");
 
@@ -309,46 +885,1089 @@ fn test_unnamed_expr_entry() {
 }
 
 #[test]
-fn test_iden_name_decl_expr() {
-    let tokens = parse_tokens_no_eof("Column");
-    let result = iden_name_decl_expr().parse(&tokens);
-    assert!(!result.has_errors());
-    let expr = result.into_output().expect("expected identifier name decl");
-    assert_eq!(expr, ExprKind::NameDecl("Column".to_string()));
-}
+fn test_name_expr() {
+    let cases = [
+        ("Column", "Column"),
+        ("$left", "$left"),
+        ("['my col']", "my col"),
+        ("[\"where\"]", "where"),
+        ("count", "count"),
+        ("bin", "bin"),
+    ];
 
-#[test]
-fn test_bracketed_name_decl_expr() {
-    let tokens = parse_tokens_no_eof("['col']");
-    let result = bracketed_name_decl_expr().parse(&tokens);
-    assert!(!result.has_errors());
-    let expr = result.into_output().expect("expected bracketed name decl");
-    assert_eq!(expr, ExprKind::NameDecl("'col'".to_string()));
+    for (input, expected) in cases {
+        let tokens = parse_tokens_no_eof(input);
+        let result = name_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "input: {input}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::Name(expected.to_string())),
+            "input: {input}"
+        );
+    }
 }
 
 #[test]
-fn test_ext_kw_as_iden_name_decl_expr() {
-    let tokens = parse_tokens_no_eof("where");
-    let result = ext_kw_as_iden_name_decl_expr().parse(&tokens);
-    assert!(!result.has_errors());
-    let expr = result
-        .into_output()
-        .expect("expected extended keyword name decl");
-    assert_eq!(expr, ExprKind::NameDecl("where".to_string()));
+fn test_name_expr_rejects_reserved_keyword() {
+    let tokens = parse_tokens_no_eof("and");
+    let result = name_expr().parse(token_input(&tokens));
+    assert!(result.has_errors());
 }
 
 #[test]
-fn test_named_expr() {
-    let tokens = parse_tokens_no_eof("where = 1");
-    let result = named_expr().parse(&tokens);
+fn test_names_in_binary_expr() {
+    let tokens = parse_tokens_no_eof("x > 5 and ['my col'] == count");
+    let result = unnamed_expr().parse(token_input(&tokens));
     assert!(!result.has_errors());
-    let expr = result.into_output().expect("expected named expr");
 
     assert_eq!(
-        expr,
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("x".to_string())),
+                op: BinOpKind::GreaterThan,
+                right: Box::new(ExprKind::Literal(LitExprKind::Long(5))),
+            }),
+            op: BinOpKind::And,
+            right: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("my col".to_string())),
+                op: BinOpKind::Equal,
+                right: Box::new(ExprKind::Name("count".to_string())),
+            }),
+        })
+    );
+}
+
+#[test]
+fn test_function_call_expr() {
+    let tokens = parse_tokens_no_eof("count()");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::FunctionCall {
+            name: "count".to_string(),
+            args: vec![],
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("bin(ts, 1h)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::FunctionCall {
+            name: "bin".to_string(),
+            args: vec![
+                ExprKind::Name("ts".to_string()),
+                ExprKind::Literal(LitExprKind::Timespan(Some(36_000_000_000))),
+            ],
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("toscalar(x)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::FunctionCall {
+            name: "toscalar".to_string(),
+            args: vec![ExprKind::Name("x".to_string())],
+        })
+    );
+}
+
+#[test]
+fn test_function_call_star_and_named_args() {
+    let tokens = parse_tokens_no_eof("count(*)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::FunctionCall {
+            name: "count".to_string(),
+            args: vec![ExprKind::Star],
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("f(x, kind=inner)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::FunctionCall {
+            name: "f".to_string(),
+            args: vec![
+                ExprKind::Name("x".to_string()),
+                ExprKind::SimpleNamed {
+                    name: Box::new(ExprKind::NameDecl("kind".to_string())),
+                    expr: Box::new(ExprKind::Name("inner".to_string())),
+                },
+            ],
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("f(*, x)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    let errors = result.errors().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(2..3));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg) if msg == "'*' is only allowed as the last argument"
+    ));
+}
+
+#[test]
+fn test_nested_function_call_in_binary_expr() {
+    let tokens = parse_tokens_no_eof("strcat(a, tostring(1 + 2)) == b");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::FunctionCall {
+                name: "strcat".to_string(),
+                args: vec![
+                    ExprKind::Name("a".to_string()),
+                    ExprKind::FunctionCall {
+                        name: "tostring".to_string(),
+                        args: vec![ExprKind::BinOp {
+                            left: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                            op: BinOpKind::Add,
+                            right: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+                        }],
+                    },
+                ],
+            }),
+            op: BinOpKind::Equal,
+            right: Box::new(ExprKind::Name("b".to_string())),
+        })
+    );
+}
+
+#[test]
+fn test_path_and_element_access() {
+    let tokens = parse_tokens_no_eof("bag.prop[0]['key']");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::ElementAccess {
+            expr: Box::new(ExprKind::ElementAccess {
+                expr: Box::new(ExprKind::Path {
+                    expr: Box::new(ExprKind::Name("bag".to_string())),
+                    selector: Box::new(ExprKind::Name("prop".to_string())),
+                }),
+                index: Box::new(ExprKind::Literal(LitExprKind::Long(0))),
+            }),
+            index: Box::new(ExprKind::Literal(LitExprKind::String {
+                value: "key".to_string(),
+                is_obfuscated: false,
+            })),
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("arr[i + 1] * 2");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::ElementAccess {
+                expr: Box::new(ExprKind::Name("arr".to_string())),
+                index: Box::new(ExprKind::BinOp {
+                    left: Box::new(ExprKind::Name("i".to_string())),
+                    op: BinOpKind::Add,
+                    right: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                }),
+            }),
+            op: BinOpKind::Multiply,
+            right: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+        })
+    );
+}
+
+#[test]
+fn test_cluster_database_path() {
+    let string_lit = |value: &str| {
+        ExprKind::Literal(LitExprKind::String {
+            value: value.to_string(),
+            is_obfuscated: false,
+        })
+    };
+
+    let tokens = parse_tokens_no_eof("cluster('c').database('d').T");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::Path {
+            expr: Box::new(ExprKind::Path {
+                expr: Box::new(ExprKind::FunctionCall {
+                    name: "cluster".to_string(),
+                    args: vec![string_lit("c")],
+                }),
+                selector: Box::new(ExprKind::FunctionCall {
+                    name: "database".to_string(),
+                    args: vec![string_lit("d")],
+                }),
+            }),
+            selector: Box::new(ExprKind::Name("T".to_string())),
+        })
+    );
+}
+
+#[test]
+fn test_unary_expr() {
+    let tokens = parse_tokens_no_eof("-x * +2");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::UnaryOp {
+                op: UnaryOpKind::Minus,
+                expr: Box::new(ExprKind::Name("x".to_string())),
+            }),
+            op: BinOpKind::Multiply,
+            right: Box::new(ExprKind::UnaryOp {
+                op: UnaryOpKind::Plus,
+                expr: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+            }),
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("not(x and - -y)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::UnaryOp {
+            op: UnaryOpKind::Not,
+            expr: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("x".to_string())),
+                op: BinOpKind::And,
+                right: Box::new(ExprKind::UnaryOp {
+                    op: UnaryOpKind::Minus,
+                    expr: Box::new(ExprKind::UnaryOp {
+                        op: UnaryOpKind::Minus,
+                        expr: Box::new(ExprKind::Name("y".to_string())),
+                    }),
+                }),
+            }),
+        })
+    );
+}
+
+#[test]
+fn test_paren_expr() {
+    let tokens = parse_tokens_no_eof("(a + b) * ((c))");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::Paren(Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("a".to_string())),
+                op: BinOpKind::Add,
+                right: Box::new(ExprKind::Name("b".to_string())),
+            }))),
+            op: BinOpKind::Multiply,
+            right: Box::new(ExprKind::Paren(Box::new(ExprKind::Paren(Box::new(
+                ExprKind::Name("c".to_string())
+            ))))),
+        })
+    );
+}
+
+#[test]
+fn test_string_operators() {
+    let cases = [
+        ("has", BinOpKind::Has),
+        ("!has_cs", BinOpKind::NotHasCs),
+        ("hasprefix", BinOpKind::HasPrefix),
+        ("!hassuffix", BinOpKind::NotHasSuffix),
+        ("contains", BinOpKind::Contains),
+        ("contains_cs", BinOpKind::ContainsCs),
+        ("containscs", BinOpKind::ContainsCs),
+        ("!contains", BinOpKind::NotContains),
+        ("notcontains", BinOpKind::NotContains),
+        ("startswith", BinOpKind::StartsWith),
+        ("!endswith_cs", BinOpKind::NotEndsWithCs),
+        ("matches regex", BinOpKind::MatchesRegex),
+        ("like", BinOpKind::Like),
+        ("notlikecs", BinOpKind::NotLikeCs),
+        ("=~", BinOpKind::EqualTilde),
+        ("!~", BinOpKind::BangTilde),
+    ];
+
+    for (op_text, op) in cases {
+        let text = format!("a {op_text} 'x'");
+        let tokens = parse_tokens_no_eof(&text);
+        let result = unnamed_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "{op_text}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("a".to_string())),
+                op,
+                right: Box::new(ExprKind::Literal(LitExprKind::String {
+                    value: "x".to_string(),
+                    is_obfuscated: false,
+                })),
+            })
+        );
+    }
+}
+
+#[test]
+fn test_string_operator_precedence() {
+    let tokens = parse_tokens_no_eof("a + b has 'x' and c =~ d");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::BinOp {
+                    left: Box::new(ExprKind::Name("a".to_string())),
+                    op: BinOpKind::Add,
+                    right: Box::new(ExprKind::Name("b".to_string())),
+                }),
+                op: BinOpKind::Has,
+                right: Box::new(ExprKind::Literal(LitExprKind::String {
+                    value: "x".to_string(),
+                    is_obfuscated: false,
+                })),
+            }),
+            op: BinOpKind::And,
+            right: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("c".to_string())),
+                op: BinOpKind::EqualTilde,
+                right: Box::new(ExprKind::Name("d".to_string())),
+            }),
+        })
+    );
+}
+
+#[test]
+fn test_in_expr() {
+    let cases = [
+        ("in", InOpKind::In),
+        ("in~", InOpKind::InCs),
+        ("!in", InOpKind::NotIn),
+        ("!in~", InOpKind::NotInCs),
+        ("has_any", InOpKind::HasAny),
+        ("has_all", InOpKind::HasAll),
+    ];
+
+    for (op_text, op) in cases {
+        let text = format!("x {op_text} (1, y, 2 + 3)");
+        let tokens = parse_tokens_no_eof(&text);
+        let result = unnamed_expr().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "{op_text}");
+        assert_eq!(
+            result.into_output(),
+            Some(ExprKind::In {
+                expr: Box::new(ExprKind::Name("x".to_string())),
+                op,
+                list: vec![
+                    ExprKind::Literal(LitExprKind::Long(1)),
+                    ExprKind::Name("y".to_string()),
+                    ExprKind::BinOp {
+                        left: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+                        op: BinOpKind::Add,
+                        right: Box::new(ExprKind::Literal(LitExprKind::Long(3))),
+                    },
+                ],
+            })
+        );
+    }
+}
+
+#[test]
+fn test_in_expr_with_dynamic_and_logical() {
+    let tokens = parse_tokens_no_eof("s has_any (dynamic(['a'])) and x !in (1)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::In {
+                expr: Box::new(ExprKind::Name("s".to_string())),
+                op: InOpKind::HasAny,
+                list: vec![ExprKind::Literal(LitExprKind::Dynamic(
+                    DynamicValue::Array(vec![DynamicValue::Scalar(Box::new(
+                        LitExprKind::String {
+                            value: "a".to_string(),
+                            is_obfuscated: false,
+                        }
+                    ))])
+                ))],
+            }),
+            op: BinOpKind::And,
+            right: Box::new(ExprKind::In {
+                expr: Box::new(ExprKind::Name("x".to_string())),
+                op: InOpKind::NotIn,
+                list: vec![ExprKind::Literal(LitExprKind::Long(1))],
+            }),
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("x in ()");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_between_expr() {
+    let tokens = parse_tokens_no_eof("x between (1 .. 10)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::Between {
+            expr: Box::new(ExprKind::Name("x".to_string())),
+            low: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+            high: Box::new(ExprKind::Literal(LitExprKind::Long(10))),
+            negated: false,
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("ts !between (ago(1d) .. now() + 1h) or y");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::BinOp {
+            left: Box::new(ExprKind::Between {
+                expr: Box::new(ExprKind::Name("ts".to_string())),
+                low: Box::new(ExprKind::FunctionCall {
+                    name: "ago".to_string(),
+                    args: vec![ExprKind::Literal(LitExprKind::Timespan(Some(
+                        864_000_000_000
+                    )))],
+                }),
+                high: Box::new(ExprKind::BinOp {
+                    left: Box::new(ExprKind::FunctionCall {
+                        name: "now".to_string(),
+                        args: vec![],
+                    }),
+                    op: BinOpKind::Add,
+                    right: Box::new(ExprKind::Literal(LitExprKind::Timespan(Some(
+                        36_000_000_000
+                    )))),
+                }),
+                negated: true,
+            }),
+            op: BinOpKind::Or,
+            right: Box::new(ExprKind::Name("y".to_string())),
+        })
+    );
+
+    let tokens = parse_tokens_no_eof("x between (1, 10)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_pipe_expr() {
+    let tokens = parse_tokens(
+        "database('db').T | take 10 | limit n + 1",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Pipe {
+                left: Box::new(ExprKind::Path {
+                    expr: Box::new(ExprKind::FunctionCall {
+                        name: "database".to_string(),
+                        args: vec![ExprKind::Literal(LitExprKind::String {
+                            value: "db".to_string(),
+                            is_obfuscated: false,
+                        })],
+                    }),
+                    selector: Box::new(ExprKind::Name("T".to_string())),
+                }),
+                operator: QueryOperator::Take {
+                    count: Box::new(ExprKind::Literal(LitExprKind::Long(10))),
+                },
+            }),
+            operator: QueryOperator::Take {
+                count: Box::new(ExprKind::BinOp {
+                    left: Box::new(ExprKind::Name("n".to_string())),
+                    op: BinOpKind::Add,
+                    right: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                }),
+            },
+        }))
+    );
+}
+
+#[test]
+fn test_pipe_expr_as_subquery() {
+    let tokens = parse_tokens_no_eof("x in (T | take 1)");
+    let result = unnamed_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::In {
+            expr: Box::new(ExprKind::Name("x".to_string())),
+            op: InOpKind::In,
+            list: vec![ExprKind::Pipe {
+                left: Box::new(ExprKind::Name("T".to_string())),
+                operator: QueryOperator::Take {
+                    count: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                },
+            }],
+        })
+    );
+
+    let tokens = parse_tokens("T | take", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_where_operator() {
+    let tokens = parse_tokens("T | where x > 1 | filter y", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Pipe {
+                left: Box::new(ExprKind::Name("T".to_string())),
+                operator: QueryOperator::Where {
+                    predicate: Box::new(ExprKind::BinOp {
+                        left: Box::new(ExprKind::Name("x".to_string())),
+                        op: BinOpKind::GreaterThan,
+                        right: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                    }),
+                },
+            }),
+            operator: QueryOperator::Where {
+                predicate: Box::new(ExprKind::Name("y".to_string())),
+            },
+        }))
+    );
+}
+
+#[test]
+fn test_where_operator_missing_predicate() {
+    for text in ["T | where", "T | where | take 1", "(T | filter)"] {
+        let tokens = parse_tokens(text, &ParseOptions::default());
+        let result = query().parse(token_input(&tokens));
+        let errors = result.errors().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1, "{text}");
+        assert!(matches!(
+            errors[0].reason(),
+            RichReason::Custom(msg) if msg.starts_with("Expected a predicate after '")
+        ));
+    }
+
+    let tokens = parse_tokens("T | where", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert_eq!(result.into_errors()[0].span(), &(4..9));
+
+    let tokens = parse_tokens("T | where x ==", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    let errors = result.into_errors();
+    assert!(!errors.is_empty());
+    assert!(!matches!(errors[0].reason(), RichReason::Custom(_)));
+}
+
+#[test]
+fn test_project_operator() {
+    let tokens = parse_tokens("T | project a, b = x + 1, f(c)", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Name("T".to_string())),
+            operator: QueryOperator::Project {
+                exprs: vec![
+                    ExprKind::Name("a".to_string()),
+                    ExprKind::SimpleNamed {
+                        name: Box::new(ExprKind::NameDecl("b".to_string())),
+                        expr: Box::new(ExprKind::BinOp {
+                            left: Box::new(ExprKind::Name("x".to_string())),
+                            op: BinOpKind::Add,
+                            right: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                        }),
+                    },
+                    ExprKind::FunctionCall {
+                        name: "f".to_string(),
+                        args: vec![ExprKind::Name("c".to_string())],
+                    },
+                ],
+            },
+        }))
+    );
+}
+
+#[test]
+fn test_project_column_operators() {
+    let cases = [
+        (
+            "T | project-away col*, *_id, ['x y']",
+            QueryOperator::ProjectAway {
+                columns: vec![
+                    ExprKind::WildcardedName("col*".to_string()),
+                    ExprKind::WildcardedName("*_id".to_string()),
+                    ExprKind::Name("x y".to_string()),
+                ],
+            },
+        ),
+        (
+            "T | project-keep a, b*c",
+            QueryOperator::ProjectKeep {
+                columns: vec![
+                    ExprKind::Name("a".to_string()),
+                    ExprKind::WildcardedName("b*c".to_string()),
+                ],
+            },
+        ),
+        (
+            "T | project-rename new = old, ['n 2'] = o2",
+            QueryOperator::ProjectRename {
+                renames: vec![
+                    ExprKind::SimpleNamed {
+                        name: Box::new(ExprKind::NameDecl("new".to_string())),
+                        expr: Box::new(ExprKind::Name("old".to_string())),
+                    },
+                    ExprKind::SimpleNamed {
+                        name: Box::new(ExprKind::NameDecl("n 2".to_string())),
+                        expr: Box::new(ExprKind::Name("o2".to_string())),
+                    },
+                ],
+            },
+        ),
+        (
+            "T | project-reorder a asc, b* desc, c granny-asc, d",
+            QueryOperator::ProjectReorder {
+                columns: vec![
+                    (ExprKind::Name("a".to_string()), Some(SortOrder::Asc)),
+                    (
+                        ExprKind::WildcardedName("b*".to_string()),
+                        Some(SortOrder::Desc),
+                    ),
+                    (ExprKind::Name("c".to_string()), Some(SortOrder::GrannyAsc)),
+                    (ExprKind::Name("d".to_string()), None),
+                ],
+            },
+        ),
+        (
+            "T | project-smart a",
+            QueryOperator::ProjectSmart {
+                exprs: vec![ExprKind::Name("a".to_string())],
+            },
+        ),
+        (
+            "T | project-by-names a*",
+            QueryOperator::ProjectByNames {
+                columns: vec![ExprKind::WildcardedName("a*".to_string())],
+            },
+        ),
+    ];
+
+    for (text, operator) in cases {
+        let tokens = parse_tokens(text, &ParseOptions::default());
+        let result = query().parse(token_input(&tokens));
+        assert!(!result.has_errors(), "{text}");
+        assert_eq!(
+            result.into_output(),
+            Some(SyntaxKind::Expr(ExprKind::Pipe {
+                left: Box::new(ExprKind::Name("T".to_string())),
+                operator,
+            }))
+        );
+    }
+}
+
+#[test]
+fn test_project_wildcard_errors() {
+    let tokens = parse_tokens("T | project-away col *", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    let errors = result.into_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(21..22));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg) if msg == "Unexpected whitespace in wildcarded name"
+    ));
+
+    let tokens = parse_tokens("T | project-away", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_extend_operator() {
+    let tokens = parse_tokens(
+        "T | extend a = x * 2, y + 1, (b, ['c d']) = f(x)",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Name("T".to_string())),
+            operator: QueryOperator::Extend {
+                exprs: vec![
+                    ExprKind::SimpleNamed {
+                        name: Box::new(ExprKind::NameDecl("a".to_string())),
+                        expr: Box::new(ExprKind::BinOp {
+                            left: Box::new(ExprKind::Name("x".to_string())),
+                            op: BinOpKind::Multiply,
+                            right: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+                        }),
+                    },
+                    ExprKind::BinOp {
+                        left: Box::new(ExprKind::Name("y".to_string())),
+                        op: BinOpKind::Add,
+                        right: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                    },
+                    ExprKind::TupleNamed {
+                        names: vec![
+                            ExprKind::NameDecl("b".to_string()),
+                            ExprKind::NameDecl("c d".to_string()),
+                        ],
+                        expr: Box::new(ExprKind::FunctionCall {
+                            name: "f".to_string(),
+                            args: vec![ExprKind::Name("x".to_string())],
+                        }),
+                    },
+                ],
+            },
+        }))
+    );
+}
+
+#[test]
+fn test_tuple_named_expr() {
+    let tokens = parse_tokens_no_eof("(a) = 1");
+    let result = named_expr(unnamed_expr()).parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(ExprKind::TupleNamed {
+            names: vec![ExprKind::NameDecl("a".to_string())],
+            expr: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+        })
+    );
+
+    let tokens = parse_tokens("T | extend (a, b + 1) = f(x)", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_summarize_operator() {
+    let tokens = parse_tokens(
+        "T | summarize c = count(), avg(x) by bin(ts, 1h), Region",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Name("T".to_string())),
+            operator: QueryOperator::Summarize {
+                hints: vec![],
+                aggregates: vec![
+                    ExprKind::SimpleNamed {
+                        name: Box::new(ExprKind::NameDecl("c".to_string())),
+                        expr: Box::new(ExprKind::FunctionCall {
+                            name: "count".to_string(),
+                            args: vec![],
+                        }),
+                    },
+                    ExprKind::FunctionCall {
+                        name: "avg".to_string(),
+                        args: vec![ExprKind::Name("x".to_string())],
+                    },
+                ],
+                by: vec![
+                    ExprKind::FunctionCall {
+                        name: "bin".to_string(),
+                        args: vec![
+                            ExprKind::Name("ts".to_string()),
+                            ExprKind::Literal(LitExprKind::Timespan(Some(36_000_000_000))),
+                        ],
+                    },
+                    ExprKind::Name("Region".to_string()),
+                ],
+            },
+        }))
+    );
+
+    for text in [
+        "T | summarize",
+        "T | summarize hint.strategy=shuffle | take 1",
+    ] {
+        let tokens = parse_tokens(text, &ParseOptions::default());
+        let errors = query().parse(token_input(&tokens)).into_errors();
+        assert_eq!(errors.len(), 1, "{text}");
+        assert_eq!(errors[0].span(), &(4..13), "{text}");
+        assert!(matches!(
+            errors[0].reason(),
+            RichReason::Custom(msg)
+                if msg == "Expected an aggregate or 'by' clause after 'summarize'"
+        ));
+    }
+}
+
+#[test]
+fn test_summarize_operator_hints() {
+    let tokens = parse_tokens(
+        "T | summarize hint.strategy=shuffle hint.shufflekey=k hint.num_partitions=10 by k",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Name("T".to_string())),
+            operator: QueryOperator::Summarize {
+                hints: vec![
+                    (HintKind::Strategy, ExprKind::Name("shuffle".to_string())),
+                    (HintKind::ShuffleKey, ExprKind::Name("k".to_string())),
+                    (
+                        HintKind::NumPartitions,
+                        ExprKind::Literal(LitExprKind::Long(10)),
+                    ),
+                ],
+                aggregates: vec![],
+                by: vec![ExprKind::Name("k".to_string())],
+            },
+        }))
+    );
+
+    let tokens = parse_tokens(
+        "T | summarize hint.remote=auto count()",
+        &ParseOptions::default(),
+    );
+    let errors = query().parse(token_input(&tokens)).into_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(14..25));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg) if msg == "Unsupported hint 'hint.remote' for 'summarize'"
+    ));
+
+    let tokens = parse_tokens("T | summarize count() by", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_join_operator() {
+    let tokens = parse_tokens(
+        "T | join kind=leftouter hint.strategy=broadcast (U | where x) on Key, $left.A == $right.B",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Name("T".to_string())),
+            operator: QueryOperator::Join {
+                kind: Some(JoinKind::LeftOuter),
+                hints: vec![(HintKind::Strategy, ExprKind::Name("broadcast".to_string()))],
+                right: Box::new(ExprKind::Paren(Box::new(ExprKind::Pipe {
+                    left: Box::new(ExprKind::Name("U".to_string())),
+                    operator: QueryOperator::Where {
+                        predicate: Box::new(ExprKind::Name("x".to_string())),
+                    },
+                }))),
+                on: vec![
+                    ExprKind::Name("Key".to_string()),
+                    ExprKind::BinOp {
+                        left: Box::new(ExprKind::Path {
+                            expr: Box::new(ExprKind::Name("$left".to_string())),
+                            selector: Box::new(ExprKind::Name("A".to_string())),
+                        }),
+                        op: BinOpKind::Equal,
+                        right: Box::new(ExprKind::Path {
+                            expr: Box::new(ExprKind::Name("$right".to_string())),
+                            selector: Box::new(ExprKind::Name("B".to_string())),
+                        }),
+                    },
+                ],
+            },
+        }))
+    );
+}
+
+#[test]
+fn test_lookup_operator() {
+    let tokens = parse_tokens(
+        "T | lookup hint.remote=auto kind=inner U on Key | take 1",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::Pipe {
+            left: Box::new(ExprKind::Pipe {
+                left: Box::new(ExprKind::Name("T".to_string())),
+                operator: QueryOperator::Lookup {
+                    kind: Some(JoinKind::Inner),
+                    hints: vec![(HintKind::Remote, ExprKind::Name("auto".to_string()))],
+                    right: Box::new(ExprKind::Name("U".to_string())),
+                    on: vec![ExprKind::Name("Key".to_string())],
+                },
+            }),
+            operator: QueryOperator::Take {
+                count: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+            },
+        }))
+    );
+
+    let tokens = parse_tokens("T | join U on Key", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+}
+
+#[test]
+fn test_join_operator_errors() {
+    let tokens = parse_tokens("T | join kind=sideways U on Key", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    let errors = result.into_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(14..22));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg) if msg == "Unknown join kind 'sideways'"
+    ));
+
+    let tokens = parse_tokens(
+        "T | lookup kind=leftanti (U) on A",
+        &ParseOptions::default(),
+    );
+    let result = query().parse(token_input(&tokens));
+    let errors = result.into_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span(), &(16..24));
+    assert!(matches!(
+        errors[0].reason(),
+        RichReason::Custom(msg)
+            if msg == "Unsupported lookup kind, expected 'leftouter' or 'inner'"
+    ));
+
+    let tokens = parse_tokens("T | join U", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(result.has_errors());
+}
+
+#[test]
+fn test_query_named_expr_with_name() {
+    let tokens = parse_tokens("y = x * 2", &ParseOptions::default());
+    let result = query().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+
+    assert_eq!(
+        result.into_output(),
+        Some(SyntaxKind::Expr(ExprKind::SimpleNamed {
+            name: Box::new(ExprKind::NameDecl("y".to_string())),
+            expr: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Name("x".to_string())),
+                op: BinOpKind::Multiply,
+                right: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+            }),
+        }))
+    );
+}
+
+#[test]
+fn test_iden_name_decl_expr() {
+    let tokens = parse_tokens_no_eof("Column");
+    let result = iden_name_decl_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    let expr = result.into_output().expect("expected identifier name decl");
+    assert_eq!(expr, ExprKind::NameDecl("Column".to_string()));
+}
+
+#[test]
+fn test_bracketed_name_decl_expr() {
+    let tokens = parse_tokens_no_eof("['col']");
+    let result = bracketed_name_decl_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    let expr = result.into_output().expect("expected bracketed name decl");
+    assert_eq!(expr, ExprKind::NameDecl("col".to_string()));
+}
+
+#[test]
+fn test_ext_kw_as_iden_name_decl_expr() {
+    let tokens = parse_tokens_no_eof("where");
+    let result = ext_kw_as_iden_name_decl_expr().parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    let expr = result
+        .into_output()
+        .expect("expected extended keyword name decl");
+    assert_eq!(expr, ExprKind::NameDecl("where".to_string()));
+}
+
+#[test]
+fn test_named_expr() {
+    let tokens = parse_tokens_no_eof("where = 1");
+    let result = named_expr(unnamed_expr()).parse(token_input(&tokens));
+    assert!(!result.has_errors());
+    let expr = result.into_output().expect("expected named expr");
+
+    assert_eq!(
+        expr,
         ExprKind::SimpleNamed {
             name: Box::new(ExprKind::NameDecl("where".to_string())),
             expr: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
         }
     );
 }
+
+#[test]
+fn test_error_span_is_source_range() {
+    let tokens = parse_tokens_no_eof("1 +    *");
+    let result = additive_expr(unnamed_expr()).parse(token_input(&tokens));
+    assert!(result.has_errors());
+    let errors = result.into_errors();
+    assert_eq!(errors[0].span(), &(7..8));
+}
+
+#[test]
+fn test_query_from_token_stream() {
+    let options = ParseOptions::default();
+    let result = query().parse(token_stream("x = 1 +  2", &options));
+    assert!(!result.has_errors());
+    let syntax = result.into_output().expect("expected query");
+
+    assert_eq!(
+        syntax,
+        SyntaxKind::Expr(ExprKind::SimpleNamed {
+            name: Box::new(ExprKind::NameDecl("x".to_string())),
+            expr: Box::new(ExprKind::BinOp {
+                left: Box::new(ExprKind::Literal(LitExprKind::Long(1))),
+                op: BinOpKind::Add,
+                right: Box::new(ExprKind::Literal(LitExprKind::Long(2))),
+            }),
+        })
+    );
+}
+
+#[test]
+fn test_token_stream_error_span() {
+    let options = ParseOptions::default();
+    let result = additive_expr(unnamed_expr()).parse(token_stream("1 +   *", &options));
+    assert!(result.has_errors());
+    assert_eq!(result.into_errors()[0].span(), &(6..7));
+}
@@ -11,10 +11,58 @@ pub enum BinOpKind {
     GreaterThanOrEqual,
     Equal,
     NotEqual,
+    EqualTilde,
+    BangTilde,
+    Has,
+    HasCs,
+    NotHas,
+    NotHasCs,
+    HasPrefix,
+    HasPrefixCs,
+    NotHasPrefix,
+    NotHasPrefixCs,
+    HasSuffix,
+    HasSuffixCs,
+    NotHasSuffix,
+    NotHasSuffixCs,
+    Contains,
+    ContainsCs,
+    NotContains,
+    NotContainsCs,
+    StartsWith,
+    StartsWithCs,
+    NotStartsWith,
+    NotStartsWithCs,
+    EndsWith,
+    EndsWithCs,
+    NotEndsWith,
+    NotEndsWithCs,
+    MatchesRegex,
+    Like,
+    LikeCs,
+    NotLike,
+    NotLikeCs,
     And,
     Or,
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub enum InOpKind {
+    In,
+    InCs,
+    NotIn,
+    NotInCs,
+    HasAny,
+    HasAll,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum UnaryOpKind {
+    Plus,
+    Minus,
+    Not,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum SyntaxKind {
     Expr(ExprKind),
@@ -23,16 +71,135 @@ pub enum SyntaxKind {
 #[derive(Debug, PartialEq, Clone)]
 pub enum ExprKind {
     Literal(LitExprKind),
+    Name(String),
+    WildcardedName(String),
     NameDecl(String),
     SimpleNamed {
         name: Box<ExprKind>,
         expr: Box<ExprKind>,
     },
+    TupleNamed {
+        names: Vec<ExprKind>,
+        expr: Box<ExprKind>,
+    },
     BinOp {
         left: Box<ExprKind>,
         op: BinOpKind,
         right: Box<ExprKind>,
     },
+    FunctionCall {
+        name: String,
+        args: Vec<ExprKind>,
+    },
+    Star,
+    Path {
+        expr: Box<ExprKind>,
+        selector: Box<ExprKind>,
+    },
+    ElementAccess {
+        expr: Box<ExprKind>,
+        index: Box<ExprKind>,
+    },
+    UnaryOp {
+        op: UnaryOpKind,
+        expr: Box<ExprKind>,
+    },
+    Paren(Box<ExprKind>),
+    In {
+        expr: Box<ExprKind>,
+        op: InOpKind,
+        list: Vec<ExprKind>,
+    },
+    Between {
+        expr: Box<ExprKind>,
+        low: Box<ExprKind>,
+        high: Box<ExprKind>,
+        negated: bool,
+    },
+    Pipe {
+        left: Box<ExprKind>,
+        operator: QueryOperator,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum QueryOperator {
+    Take {
+        count: Box<ExprKind>,
+    },
+    Where {
+        predicate: Box<ExprKind>,
+    },
+    Extend {
+        exprs: Vec<ExprKind>,
+    },
+    Summarize {
+        hints: Vec<(HintKind, ExprKind)>,
+        aggregates: Vec<ExprKind>,
+        by: Vec<ExprKind>,
+    },
+    Join {
+        kind: Option<JoinKind>,
+        hints: Vec<(HintKind, ExprKind)>,
+        right: Box<ExprKind>,
+        on: Vec<ExprKind>,
+    },
+    Lookup {
+        kind: Option<JoinKind>,
+        hints: Vec<(HintKind, ExprKind)>,
+        right: Box<ExprKind>,
+        on: Vec<ExprKind>,
+    },
+    Project {
+        exprs: Vec<ExprKind>,
+    },
+    ProjectAway {
+        columns: Vec<ExprKind>,
+    },
+    ProjectKeep {
+        columns: Vec<ExprKind>,
+    },
+    ProjectRename {
+        renames: Vec<ExprKind>,
+    },
+    ProjectReorder {
+        columns: Vec<(ExprKind, Option<SortOrder>)>,
+    },
+    ProjectSmart {
+        exprs: Vec<ExprKind>,
+    },
+    ProjectByNames {
+        columns: Vec<ExprKind>,
+    },
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum JoinKind {
+    FullOuter,
+    Inner,
+    InnerUnique,
+    LeftAnti,
+    LeftOuter,
+    LeftSemi,
+    RightAnti,
+    RightOuter,
+    RightSemi,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum HintKind {
+    NumPartitions,
+    Remote,
+    ShuffleKey,
+    Strategy,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+    GrannyAsc,
+    GrannyDesc,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -40,5 +207,35 @@ pub enum LitExprKind {
     Boolean(bool),
     Long(i64),
     Real(f64),
-    String(String),
+    /// Ticks (100ns units) since 0001-01-01T00:00:00Z, `None` for `datetime(null)`.
+    DateTime(Option<i64>),
+    Decimal(Option<DecimalValue>),
+    Dynamic(DynamicValue),
+    Guid(Option<u128>),
+    Int(Option<i32>),
+    /// Ticks (100ns units), `None` for `time(null)`.
+    Timespan(Option<i64>),
+    String {
+        value: String,
+        /// Set when any part was written with the `h` prefix.
+        is_obfuscated: bool,
+    },
+}
+
+/// Decimal number equal to `digits * 10^-scale`, negated when `is_negative` is set.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DecimalValue {
+    pub is_negative: bool,
+    /// Decimal digits without leading zeros, "0" for zero.
+    pub digits: String,
+    pub scale: u32,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DynamicValue {
+    Null,
+    Scalar(Box<LitExprKind>),
+    Array(Vec<DynamicValue>),
+    /// Properties in the order they were written.
+    Object(Vec<(String, DynamicValue)>),
 }
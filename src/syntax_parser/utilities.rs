@@ -1,10 +1,33 @@
 use crate::{
     parser_return,
-    token_parser::{PunctuationKind, TokenKind},
+    token_parser::{ParseOptions, PunctuationKind, Token, TokenKind, Tokenizer},
 };
-use chumsky::prelude::*;
+use chumsky::{
+    input::{Stream, ValueInput},
+    prelude::*,
+};
+use std::ops::Range;
+
+pub trait TokenInput<'a>: ValueInput<'a, Token = TokenKind<'a>, Span = Range<usize>> {}
+
+impl<'a, T> TokenInput<'a> for T where T: ValueInput<'a, Token = TokenKind<'a>, Span = Range<usize>> {}
+
+/// Adapts a token slice into a parser input whose spans are source byte ranges.
+pub fn token_input<'a>(tokens: &'a [Token<'a>]) -> impl TokenInput<'a> + Clone {
+    let end = tokens.last().map_or(0, |token| token.span.end);
+    tokens.map(end..end, |token: &Token<'a>| (&token.kind, &token.span))
+}
+
+/// Adapts a lazily tokenized source into a parser input, lexing tokens only as they are needed.
+pub fn token_stream<'a>(text: &'a str, options: &ParseOptions) -> impl TokenInput<'a> {
+    let end = text.len();
+    let tokens = Tokenizer::new(text, options).map(|token| (token.kind, token.span));
+    Stream::from_iter(tokens).map(end..end, |(kind, span)| (kind, span))
+}
 
-pub(crate) fn punct_token<'a>(kind: PunctuationKind) -> parser_return!(PunctuationKind) {
+pub(crate) fn punct_token<'a, I: TokenInput<'a>>(
+    kind: PunctuationKind,
+) -> parser_return!(PunctuationKind) {
     select! {
         TokenKind::Punctuation(p) if p == kind => p,
     }
@@ -0,0 +1,661 @@
+use crate::syntax_parser::DecimalValue;
+use std::{fmt::Display, ops::Range};
+
+pub(crate) const TICKS_PER_SECOND: i64 = 10_000_000;
+pub(crate) const TICKS_PER_MINUTE: i64 = 60 * TICKS_PER_SECOND;
+pub(crate) const TICKS_PER_HOUR: i64 = 60 * TICKS_PER_MINUTE;
+pub(crate) const TICKS_PER_DAY: i64 = 24 * TICKS_PER_HOUR;
+
+const MAX_DATETIME_TICKS: i64 = get_days_before_year(10000) * TICKS_PER_DAY - 1;
+const NANOS_PER_TICK: i128 = 100;
+const MAX_FRACTION_DIGITS: usize = 7;
+const TWO_DIGIT_YEAR_MAX: u32 = 2049;
+const GUID_HEX_DIGITS: usize = 32;
+const GUID_DASH_POSITIONS: [usize; 4] = [8, 13, 18, 23];
+const MAX_DECIMAL_EXPONENT: u32 = 1000;
+
+const MONTH_NAMES: &[&str] = &[
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+const WEEKDAY_NAMES: &[(&str, &str)] = &[
+    ("Mon", "Monday"),
+    ("Tue", "Tuesday"),
+    ("Wed", "Wednesday"),
+    ("Thu", "Thursday"),
+    ("Fri", "Friday"),
+    ("Sat", "Saturday"),
+    ("Sun", "Sunday"),
+];
+const UTC_ZONE_NAMES: &[&str] = &["GMT", "UTC", "UT", "Z"];
+const TIMESPAN_UNITS: &[(&[&str], i128)] = &[
+    (
+        &["d", "day", "days"],
+        TICKS_PER_DAY as i128 * NANOS_PER_TICK,
+    ),
+    (
+        &["h", "hr", "hrs", "hour", "hours"],
+        TICKS_PER_HOUR as i128 * NANOS_PER_TICK,
+    ),
+    (
+        &["m", "min", "minute", "minutes"],
+        TICKS_PER_MINUTE as i128 * NANOS_PER_TICK,
+    ),
+    (
+        &["s", "sec", "second", "seconds"],
+        TICKS_PER_SECOND as i128 * NANOS_PER_TICK,
+    ),
+    (
+        &[
+            "ms",
+            "milli",
+            "millis",
+            "millisec",
+            "millisecond",
+            "milliseconds",
+        ],
+        1_000_000,
+    ),
+    (
+        &["micro", "micros", "microsec", "microsecond", "microseconds"],
+        1_000,
+    ),
+    (
+        &["nano", "nanos", "nanosec", "nanosecond", "nanoseconds"],
+        1,
+    ),
+    (&["tick", "ticks"], NANOS_PER_TICK),
+];
+
+/// Error in a literal value, with `span` relative to the value text.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct ValueError {
+    pub(crate) span: Range<usize>,
+    pub(crate) message: String,
+}
+
+/// Parses the ISO 8601, RFC 822 and RFC 850 forms Kusto accepts into ticks (100ns units) since
+/// 0001-01-01T00:00:00Z.
+pub(crate) fn parse_datetime(text: &str) -> Result<i64, ValueError> {
+    let mut cursor = Cursor::new(text);
+    let bytes = text.as_bytes();
+
+    let ticks = match cursor.peek() {
+        Some(_) if bytes.len() > 4 && bytes[..4].iter().all(u8::is_ascii_digit) => {
+            parse_iso_datetime(&mut cursor)?
+        }
+        Some(byte) if byte.is_ascii_digit() => parse_rfc_datetime(&mut cursor)?.1,
+        Some(byte) if byte.is_ascii_alphabetic() => {
+            let (name, span) = cursor.take_while(|b| b.is_ascii_alphabetic());
+            let weekday = WEEKDAY_NAMES
+                .iter()
+                .position(|(short, long)| {
+                    name.eq_ignore_ascii_case(short) || name.eq_ignore_ascii_case(long)
+                })
+                .ok_or_else(|| ValueError {
+                    span: span.clone(),
+                    message: format!("unknown day of week '{name}'"),
+                })?;
+
+            cursor.expect(b',')?;
+            cursor.skip_spaces();
+
+            let (days, ticks) = parse_rfc_datetime(&mut cursor)?;
+
+            // 0001-01-01 was a Monday
+            if days % 7 != weekday as i64 {
+                return Err(ValueError {
+                    span,
+                    message: format!(
+                        "day of week '{name}' does not match the date, which is a {}",
+                        WEEKDAY_NAMES[(days % 7) as usize].1
+                    ),
+                });
+            }
+
+            ticks
+        }
+        _ => return Err(cursor.unexpected("expected a date")),
+    };
+
+    if !cursor.is_at_end() {
+        return Err(cursor.unexpected("expected end of datetime"));
+    }
+
+    if !(0..=MAX_DATETIME_TICKS).contains(&ticks) {
+        return Err(ValueError {
+            span: 0..text.len(),
+            message: "datetime is out of range".to_string(),
+        });
+    }
+
+    Ok(ticks)
+}
+
+/// Parses a GUID written as 32 hex digits, optionally grouped as 8-4-4-4-12 and enclosed in braces
+/// or parentheses.
+pub(crate) fn parse_guid(text: &str) -> Result<u128, ValueError> {
+    let (digits, offset) = match text.as_bytes() {
+        [b'{', .., b'}'] | [b'(', .., b')'] => (&text[1..text.len() - 1], 1),
+        _ => (text, 0),
+    };
+
+    let is_grouped = digits.len() == GUID_HEX_DIGITS + GUID_DASH_POSITIONS.len();
+    if digits.len() != GUID_HEX_DIGITS && !is_grouped {
+        return Err(ValueError {
+            span: 0..text.len(),
+            message: "expected 32 hex digits, optionally grouped as 8-4-4-4-12".to_string(),
+        });
+    }
+
+    let mut value = 0;
+    for (index, c) in digits.char_indices() {
+        let span = offset + index..offset + index + c.len_utf8();
+
+        if is_grouped && GUID_DASH_POSITIONS.contains(&index) {
+            if c != '-' {
+                return Err(ValueError {
+                    span,
+                    message: format!("expected '-', found '{c}'"),
+                });
+            }
+            continue;
+        }
+
+        let digit = c.to_digit(16).ok_or_else(|| ValueError {
+            span,
+            message: format!("expected hex digit, found '{c}'"),
+        })?;
+        value = value << 4 | digit as u128;
+    }
+
+    Ok(value)
+}
+
+/// Parses a decimal number with an optional sign and exponent, keeping every digit.
+pub(crate) fn parse_decimal(text: &str) -> Result<DecimalValue, ValueError> {
+    let mut cursor = Cursor::new(text);
+    let is_negative = cursor.eat(b'-');
+    if !is_negative {
+        cursor.eat(b'+');
+    }
+
+    let whole = cursor.take_while(|b| b.is_ascii_digit()).0;
+    let fraction = if cursor.eat(b'.') {
+        cursor.take_while(|b| b.is_ascii_digit()).0
+    } else {
+        ""
+    };
+
+    if whole.is_empty() && fraction.is_empty() {
+        return Err(cursor.unexpected("expected number"));
+    }
+
+    let mut exponent = 0;
+    if cursor.eat(b'e') || cursor.eat(b'E') {
+        let is_negative_exponent = cursor.eat(b'-');
+        if !is_negative_exponent {
+            cursor.eat(b'+');
+        }
+
+        let (digits, span) = cursor.take_while(|b| b.is_ascii_digit());
+        if digits.is_empty() {
+            return Err(cursor.unexpected("expected exponent"));
+        }
+
+        exponent = digits
+            .parse::<u32>()
+            .ok()
+            .filter(|exponent| *exponent <= MAX_DECIMAL_EXPONENT)
+            .ok_or_else(|| ValueError {
+                span,
+                message: format!("exponent is larger than {MAX_DECIMAL_EXPONENT}"),
+            })? as i64;
+
+        if is_negative_exponent {
+            exponent = -exponent;
+        }
+    }
+
+    if !cursor.is_at_end() {
+        return Err(cursor.unexpected("expected end of decimal"));
+    }
+
+    let mut digits = format!("{whole}{fraction}");
+    let mut scale = fraction.len() as i64 - exponent;
+    if scale < 0 {
+        digits.extend(std::iter::repeat_n('0', -scale as usize));
+        scale = 0;
+    }
+
+    let digits = match digits.trim_start_matches('0') {
+        "" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    };
+
+    Ok(DecimalValue {
+        is_negative: is_negative && digits != "0",
+        digits,
+        scale: scale as u32,
+    })
+}
+
+/// Parses a timespan like `1.5h` or the goo forms `[-]d`, `[-]n unit` and
+/// `[-][d.]hh:mm[:ss[.fffffff]]` into ticks (100ns units).
+pub(crate) fn parse_timespan(text: &str) -> Result<i64, ValueError> {
+    let mut cursor = Cursor::new(text);
+    let sign = if cursor.eat(b'-') { -1 } else { 1 };
+
+    let nanos = if text.contains(':') {
+        parse_clock_timespan(&mut cursor)? * NANOS_PER_TICK
+    } else {
+        parse_unit_timespan(&mut cursor)?
+    };
+
+    if !cursor.is_at_end() {
+        return Err(cursor.unexpected("expected end of timespan"));
+    }
+
+    i64::try_from(sign * nanos / NANOS_PER_TICK).map_err(|_| ValueError {
+        span: 0..text.len(),
+        message: "timespan is out of range".to_string(),
+    })
+}
+
+// n[.n][ ][unit], in days when the unit is omitted
+fn parse_unit_timespan(cursor: &mut Cursor) -> Result<i128, ValueError> {
+    let (whole, whole_span) = cursor.take_while(|b| b.is_ascii_digit());
+    if whole.is_empty() {
+        return Err(cursor.unexpected("expected number"));
+    }
+
+    let fraction = if cursor.eat(b'.') {
+        cursor.take_while(|b| b.is_ascii_digit()).0
+    } else {
+        ""
+    };
+    let number_span = whole_span.start..cursor.pos;
+
+    cursor.skip_spaces();
+    let (unit, unit_span) = cursor.take_while(|b| b.is_ascii_alphabetic());
+    let nanos_per_unit = if unit.is_empty() {
+        TIMESPAN_UNITS[0].1
+    } else {
+        TIMESPAN_UNITS
+            .iter()
+            .find(|(names, _)| names.iter().any(|name| unit.eq_ignore_ascii_case(name)))
+            .map(|(_, nanos)| *nanos)
+            .ok_or_else(|| ValueError {
+                span: unit_span,
+                message: format!("unknown timespan unit '{unit}'"),
+            })?
+    };
+
+    let out_of_range = || ValueError {
+        span: number_span.clone(),
+        message: "timespan is out of range".to_string(),
+    };
+    let whole_nanos = whole
+        .parse::<i128>()
+        .ok()
+        .and_then(|whole| whole.checked_mul(nanos_per_unit))
+        .ok_or_else(out_of_range)?;
+
+    // Digits past the nanosecond precision of any unit cannot affect the ticks
+    let fraction = &fraction[..fraction.len().min(20)];
+    let fraction_nanos = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse::<i128>().unwrap_or_default() * nanos_per_unit
+            / 10_i128.pow(fraction.len() as u32)
+    };
+
+    whole_nanos
+        .checked_add(fraction_nanos)
+        .ok_or_else(out_of_range)
+}
+
+// [d.]hh:mm[:ss[.fffffff]]
+fn parse_clock_timespan(cursor: &mut Cursor) -> Result<i128, ValueError> {
+    let (first, first_span) = cursor.number("days", 1, 8)?;
+
+    let (days, hours) = if cursor.eat(b'.') {
+        (first, cursor.ranged_number("hours", 1, 2, 23)?)
+    } else if first > 23 {
+        return Err(ValueError {
+            span: first_span,
+            message: format!("hours {first} is out of range"),
+        });
+    } else {
+        (0, first)
+    };
+
+    cursor.expect(b':')?;
+    let minutes = cursor.ranged_number("minutes", 1, 2, 59)?;
+    let mut ticks = days as i128 * TICKS_PER_DAY as i128
+        + hours as i128 * TICKS_PER_HOUR as i128
+        + minutes as i128 * TICKS_PER_MINUTE as i128;
+
+    if cursor.eat(b':') {
+        let seconds = cursor.ranged_number("seconds", 1, 2, 59)?;
+        ticks += seconds as i128 * TICKS_PER_SECOND as i128;
+
+        if cursor.eat(b'.') {
+            ticks += parse_fraction(cursor)? as i128;
+        }
+    }
+
+    Ok(ticks)
+}
+
+// yyyy-MM-dd[(T| )HH:mm[:ss[.fffffff]][Z|(+|-)HH[:]mm]]
+fn parse_iso_datetime(cursor: &mut Cursor) -> Result<i64, ValueError> {
+    let (year, year_span) = cursor.number("year", 4, 4)?;
+    cursor.expect(b'-')?;
+    let (month, month_span) = cursor.number("month", 2, 2)?;
+    cursor.expect(b'-')?;
+    let (day, day_span) = cursor.number("day", 2, 2)?;
+
+    let days = get_days_from_date((year, year_span), (month, month_span), (day, day_span))?;
+    let mut ticks = days * TICKS_PER_DAY;
+
+    let has_time = match cursor.peek() {
+        Some(b'T' | b't') => true,
+        Some(b' ') => cursor.peek_at(1).is_some_and(|b| b.is_ascii_digit()),
+        _ => false,
+    };
+
+    if has_time {
+        cursor.pos += 1;
+        ticks += parse_time(cursor)?;
+
+        if cursor.eat(b'Z') || cursor.eat(b'z') {
+            return Ok(ticks);
+        }
+
+        if matches!(cursor.peek(), Some(b'+' | b'-')) {
+            ticks -= parse_zone_offset(cursor)?;
+        }
+    }
+
+    Ok(ticks)
+}
+
+// d MMM yy HH:mm[:ss] [zone] or dd-MMM-yy HH:mm[:ss] [zone], returning the local date as days
+// alongside the ticks
+fn parse_rfc_datetime(cursor: &mut Cursor) -> Result<(i64, i64), ValueError> {
+    let day = cursor.number("day", 1, 2)?;
+    let is_dashed = cursor.eat(b'-');
+    if !is_dashed && !cursor.skip_spaces() {
+        return Err(cursor.unexpected("expected ' ' or '-' after day"));
+    }
+
+    let (name, month_span) = cursor.take_while(|b| b.is_ascii_alphabetic());
+    let month = MONTH_NAMES
+        .iter()
+        .position(|month| name.eq_ignore_ascii_case(month))
+        .ok_or_else(|| {
+            if name.is_empty() {
+                cursor.unexpected("expected month name")
+            } else {
+                ValueError {
+                    span: month_span.clone(),
+                    message: format!("unknown month name '{name}'"),
+                }
+            }
+        })?;
+
+    if is_dashed {
+        cursor.expect(b'-')?;
+    } else if !cursor.skip_spaces() {
+        return Err(cursor.unexpected("expected ' ' after month"));
+    }
+
+    let (year, year_span) = cursor.number("year", 2, 4)?;
+    let year = match year_span.len() {
+        2 if year + 2000 <= TWO_DIGIT_YEAR_MAX => year + 2000,
+        2 => year + 1900,
+        4 => year,
+        _ => {
+            return Err(ValueError {
+                span: year_span,
+                message: "expected 2 or 4 digit year".to_string(),
+            });
+        }
+    };
+
+    let days = get_days_from_date((year, year_span), (month as u32 + 1, month_span), day)?;
+
+    if !cursor.skip_spaces() {
+        return Err(cursor.unexpected("expected ' ' before time"));
+    }
+
+    let mut ticks = days * TICKS_PER_DAY + parse_time(cursor)?;
+
+    if cursor.skip_spaces() {
+        if matches!(cursor.peek(), Some(b'+' | b'-')) {
+            ticks -= parse_zone_offset(cursor)?;
+        } else {
+            let (zone, span) = cursor.take_while(|b| b.is_ascii_alphabetic());
+            if zone.is_empty() {
+                return Err(cursor.unexpected("expected time zone"));
+            }
+            if !UTC_ZONE_NAMES.iter().any(|z| zone.eq_ignore_ascii_case(z)) {
+                return Err(ValueError {
+                    span,
+                    message: format!("unknown time zone '{zone}'"),
+                });
+            }
+        }
+    }
+
+    Ok((days, ticks))
+}
+
+// HH:mm[:ss[.fffffff]]
+fn parse_time(cursor: &mut Cursor) -> Result<i64, ValueError> {
+    let hour = cursor.ranged_number("hour", 2, 2, 23)?;
+    cursor.expect(b':')?;
+    let minute = cursor.ranged_number("minute", 2, 2, 59)?;
+    let mut ticks = hour as i64 * TICKS_PER_HOUR + minute as i64 * TICKS_PER_MINUTE;
+
+    if cursor.eat(b':') {
+        let second = cursor.ranged_number("second", 2, 2, 59)?;
+        ticks += second as i64 * TICKS_PER_SECOND;
+
+        if cursor.eat(b'.') {
+            ticks += parse_fraction(cursor)?;
+        }
+    }
+
+    Ok(ticks)
+}
+
+/// Parses up to 7 fractional second digits into ticks.
+pub(crate) fn parse_fraction(cursor: &mut Cursor) -> Result<i64, ValueError> {
+    let (digits, span) = cursor.take_while(|b| b.is_ascii_digit());
+
+    if digits.is_empty() {
+        return Err(cursor.unexpected("expected fraction of a second"));
+    }
+    if digits.len() > MAX_FRACTION_DIGITS {
+        return Err(ValueError {
+            span,
+            message: format!("fraction of a second has more than {MAX_FRACTION_DIGITS} digits"),
+        });
+    }
+
+    let scale = 10_i64.pow((MAX_FRACTION_DIGITS - digits.len()) as u32);
+    Ok(digits.parse::<i64>().unwrap_or_default() * scale)
+}
+
+// (+|-)HH[:]mm
+fn parse_zone_offset(cursor: &mut Cursor) -> Result<i64, ValueError> {
+    let sign = if cursor.eat(b'-') { -1 } else { 1 };
+    cursor.eat(b'+');
+
+    let hours = cursor.ranged_number("time zone hour", 2, 2, 14)?;
+    cursor.eat(b':');
+    let minutes = cursor.ranged_number("time zone minute", 2, 2, 59)?;
+
+    Ok(sign * (hours as i64 * TICKS_PER_HOUR + minutes as i64 * TICKS_PER_MINUTE))
+}
+
+fn get_days_from_date(
+    (year, year_span): (u32, Range<usize>),
+    (month, month_span): (u32, Range<usize>),
+    (day, day_span): (u32, Range<usize>),
+) -> Result<i64, ValueError> {
+    if !(1..=9999).contains(&year) {
+        return Err(ValueError {
+            span: year_span,
+            message: format!("year {year} is out of range"),
+        });
+    }
+    if !(1..=12).contains(&month) {
+        return Err(ValueError {
+            span: month_span,
+            message: format!("month {month} is out of range"),
+        });
+    }
+
+    let is_leap_year =
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let days_in_month = match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+
+    if !(1..=days_in_month).contains(&day) {
+        return Err(ValueError {
+            span: day_span,
+            message: format!("day {day} is out of range for {year:04}-{month:02}"),
+        });
+    }
+
+    const DAYS_BEFORE_MONTH: [u32; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+    let leap_day = (is_leap_year && month > 2) as u32;
+    let day_of_year = DAYS_BEFORE_MONTH[month as usize - 1] + leap_day + day - 1;
+
+    Ok(get_days_before_year(year as i64) + day_of_year as i64)
+}
+
+const fn get_days_before_year(year: i64) -> i64 {
+    let y = year - 1;
+    y * 365 + y / 4 - y / 100 + y / 400
+}
+
+pub(crate) struct Cursor<'a> {
+    text: &'a str,
+    pub(crate) pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    pub(crate) fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    pub(crate) fn peek_at(&self, offset: usize) -> Option<u8> {
+        self.text.as_bytes().get(self.pos + offset).copied()
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.pos >= self.text.len()
+    }
+
+    pub(crate) fn eat(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn expect(&mut self, byte: u8) -> Result<(), ValueError> {
+        if self.eat(byte) {
+            Ok(())
+        } else {
+            Err(self.unexpected(format!("expected '{}'", byte as char)))
+        }
+    }
+
+    pub(crate) fn skip_spaces(&mut self) -> bool {
+        !self.take_while(|b| b.is_ascii_whitespace()).0.is_empty()
+    }
+
+    pub(crate) fn take_while(
+        &mut self,
+        predicate: impl Fn(&u8) -> bool,
+    ) -> (&'a str, Range<usize>) {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| predicate(&b)) {
+            self.pos += 1;
+        }
+
+        (&self.text[start..self.pos], start..self.pos)
+    }
+
+    pub(crate) fn number(
+        &mut self,
+        what: &str,
+        min_len: usize,
+        max_len: usize,
+    ) -> Result<(u32, Range<usize>), ValueError> {
+        let (digits, span) = self.take_while(|b| b.is_ascii_digit());
+
+        if digits.is_empty() {
+            return Err(self.unexpected(format!("expected {what}")));
+        }
+        if !(min_len..=max_len).contains(&digits.len()) {
+            let len = if min_len == max_len {
+                min_len.to_string()
+            } else {
+                format!("{min_len} to {max_len}")
+            };
+            return Err(ValueError {
+                span,
+                message: format!("expected {len} digit {what}"),
+            });
+        }
+
+        Ok((digits.parse().unwrap_or_default(), span))
+    }
+
+    fn ranged_number(
+        &mut self,
+        what: &str,
+        min_len: usize,
+        max_len: usize,
+        max: u32,
+    ) -> Result<u32, ValueError> {
+        let (value, span) = self.number(what, min_len, max_len)?;
+
+        if value > max {
+            return Err(ValueError {
+                span,
+                message: format!("{what} {value} is out of range"),
+            });
+        }
+
+        Ok(value)
+    }
+
+    pub(crate) fn unexpected(&self, expected: impl Display) -> ValueError {
+        match self.text[self.pos..].chars().next() {
+            Some(c) => ValueError {
+                span: self.pos..self.pos + c.len_utf8(),
+                message: format!("{expected}, found '{c}'"),
+            },
+            None => ValueError {
+                span: self.pos..self.pos,
+                message: format!("{expected}, found end of value"),
+            },
+        }
+    }
+}
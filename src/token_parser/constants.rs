@@ -1,4 +1,7 @@
-use super::KeywordKind;
+use super::{
+    KeywordKind,
+    keyword_trie::{KeywordTrie, keyword_bytes_len},
+};
 
 pub(crate) const UTF8_1_BYTE_MASK: u8 = 0b1000_0000;
 pub(crate) const UTF8_1_BYTE_PATTERN: u8 = 0b0000_0000;
@@ -9,6 +12,8 @@ pub(crate) const UTF8_3_BYTE_PATTERN: u8 = 0b1110_0000;
 pub(crate) const UTF8_4_BYTE_MASK: u8 = 0b1111_1000;
 pub(crate) const UTF8_4_BYTE_PATTERN: u8 = 0b1111_0000;
 pub(crate) const AVG_BYTES_PER_TOKEN: usize = 5;
+// Bytes a token may peek past its end outside of literals, e.g. `1.` checking for `..`
+pub(crate) const MAX_TOKEN_LOOKAHEAD: usize = 2;
 pub(crate) const MULTI_LINE_STRING_SEQUENCES: &[&[u8]] = &[b"```", b"~~~"];
 pub(crate) const BOOL_LITERALS: &[&[u8]] =
     &[b"true", b"false", b"True", b"False", b"TRUE", b"FALSE"];
@@ -36,6 +41,7 @@ pub(crate) const TIMESPAN_SUFFIXES: &[&[u8]] = &[
     b"nano",
     b"ticks",
     b"tick",
+    b"days",
     b"hrs",
     b"sec",
     b"min",
@@ -200,6 +206,7 @@ pub(crate) const KEYWORDS: &[(&[u8], KeywordKind)] = &[
     (b"rowstore", KeywordKind::Rowstore),
     (b"timeline", KeywordKind::Timeline),
     (b"toscalar", KeywordKind::ToScalar),
+    (b"!between", KeywordKind::NotBetween),
     (b"datetime", KeywordKind::DateTime),
     (b"timespan", KeywordKind::Timespan),
     (b"uniqueid", KeywordKind::UniqueId),
@@ -211,6 +218,7 @@ pub(crate) const KEYWORDS: &[(&[u8], KeywordKind)] = &[
     (b"default", KeywordKind::Default),
     (b"has_any", KeywordKind::HasAny),
     (b"has_all", KeywordKind::HasAll),
+    (b"!has_cs", KeywordKind::NotHasCs),
     (b"isfuzzy", KeywordKind::IsFuzzy),
     (b"journal", KeywordKind::Journal),
     (b"missing", KeywordKind::Missing),
@@ -327,3 +335,5 @@ pub(crate) const KEYWORDS: &[(&[u8], KeywordKind)] = &[
     (b"or", KeywordKind::Or),
     (b"to", KeywordKind::To),
 ];
+pub(crate) const KEYWORD_TRIE_CAPACITY: usize = keyword_bytes_len(KEYWORDS) + 1;
+pub(crate) static KEYWORD_TRIE: KeywordTrie<KEYWORD_TRIE_CAPACITY> = KeywordTrie::new(KEYWORDS);
@@ -0,0 +1,204 @@
+use super::parser::scan_token;
+use super::{LexDiagnostic, LiteralKind, ParseOptions, PunctuationKind, Token, TokenKind, Trivia};
+use super::{constants::MAX_TOKEN_LOOKAHEAD, utilities::*};
+use std::{borrow::Cow, ops::Range};
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct TextEdit<'a> {
+    /// Byte range of the previous text that was replaced.
+    pub range: Range<usize>,
+    pub replacement: &'a str,
+}
+
+/// Re-tokenizes `text`, the result of applying `edit` to the text `previous` was produced from.
+///
+/// Scanning restarts shortly before the line containing the edit and stops as soon as a token
+/// boundary past the edit lines up with a previous one, reusing the remaining tokens shifted by
+/// the length difference of the edit. `options` must match the ones used for `previous`.
+pub fn reparse_tokens<'a>(
+    text: &'a str,
+    previous: &[Token<'_>],
+    edit: &TextEdit,
+    options: &ParseOptions,
+) -> Vec<Token<'a>> {
+    reparse_tokens_with_diagnostics(text, previous, &[], edit, options).0
+}
+
+/// Like `reparse_tokens`, also splicing the diagnostics of the rescanned tokens into
+/// `previous_diagnostics`, the diagnostics `previous` was produced with.
+pub fn reparse_tokens_with_diagnostics<'a>(
+    text: &'a str,
+    previous: &[Token<'_>],
+    previous_diagnostics: &[LexDiagnostic],
+    edit: &TextEdit,
+    options: &ParseOptions,
+) -> (Vec<Token<'a>>, Vec<LexDiagnostic>) {
+    let new_edit_end = edit.range.start + edit.replacement.len();
+    debug_assert_eq!(
+        text.get(edit.range.start..new_edit_end),
+        Some(edit.replacement)
+    );
+
+    let delta = new_edit_end as isize - edit.range.end as isize;
+    let restart = get_restart_index(text.as_bytes(), previous, edit.range.start, options);
+    let mut tokens = Vec::with_capacity(previous.len());
+    tokens.extend(previous[..restart].iter().map(|t| rebase_token(t, text, 0)));
+
+    let mut pos = previous
+        .get(restart)
+        .map_or(0, |token| token.full_span().start);
+    let mut old_index = restart;
+    let mut diagnostics: Vec<_> = previous_diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.span.start < pos)
+        .cloned()
+        .collect();
+
+    loop {
+        if pos >= new_edit_end {
+            // Past the edit the text is unchanged, so once a token starts where a previous one
+            // started (shifted by `delta`) every following token is the same as before.
+            let old_pos = (pos as isize - delta) as usize;
+            while let Some(token) = previous.get(old_index)
+                && token.full_span().start < old_pos
+            {
+                old_index += 1;
+            }
+
+            // The end token depends on the trailing trivia of the token before it, so it is
+            // always rescanned. Diagnostics overlapping the boundary suppress the ones of the
+            // tokens they cover, so scanning continues past them.
+            if let Some(token) = previous.get(old_index)
+                && token.full_span().start == old_pos
+                && token.kind != TokenKind::EndOfFile
+                && diagnostics.last().is_none_or(|d| d.span.end <= pos)
+                && !previous_diagnostics
+                    .iter()
+                    .any(|d| d.span.start < old_pos && d.span.end > old_pos)
+            {
+                tokens.extend(
+                    previous[old_index..]
+                        .iter()
+                        .map(|t| rebase_token(t, text, delta)),
+                );
+                diagnostics.extend(
+                    previous_diagnostics
+                        .iter()
+                        .filter(|diagnostic| diagnostic.span.start >= old_pos)
+                        .map(|diagnostic| LexDiagnostic {
+                            span: shift_range(&diagnostic.span, delta),
+                            ..diagnostic.clone()
+                        }),
+                );
+                break;
+            }
+        }
+
+        let has_prior_trivia = tokens
+            .last()
+            .is_some_and(|token| !token.trailing_trivia.is_empty());
+        let Some(token) = scan_token(text, pos, has_prior_trivia, options, &mut diagnostics) else {
+            break;
+        };
+
+        let is_eof = token.kind == TokenKind::EndOfFile;
+        pos = token.full_span().end;
+        tokens.push(token);
+
+        if is_eof {
+            break;
+        }
+    }
+
+    (tokens, diagnostics)
+}
+
+fn get_restart_index(
+    bytes: &[u8],
+    previous: &[Token<'_>],
+    edit_start: usize,
+    options: &ParseOptions,
+) -> usize {
+    // The text before the edit is unchanged, so the line start can be found in the new text
+    let line_start = bytes[..edit_start]
+        .iter()
+        .rposition(|&b| is_line_break_start(b))
+        .map_or(0, |break_pos| break_pos + 1);
+
+    // Tokens on the previous line may peek a few bytes past its line break
+    let safe_pos = line_start.saturating_sub(MAX_TOKEN_LOOKAHEAD + 1);
+    let restart = previous
+        .partition_point(|token| token.full_span().start <= safe_pos)
+        .saturating_sub(1);
+
+    // Unterminated multi-line strings and goo literals allowed to span lines look ahead
+    // arbitrarily far, so the edit may complete them
+    (0..restart)
+        .find(|&index| has_unbounded_lookahead(previous, index, options))
+        .unwrap_or(restart)
+}
+
+fn has_unbounded_lookahead(tokens: &[Token<'_>], index: usize, options: &ParseOptions) -> bool {
+    match &tokens[index].kind {
+        TokenKind::Bad(text) => text == "`" || text == "~",
+        TokenKind::Keyword(kind) => {
+            options.allow_literals_with_line_breaks
+                && is_goo_literal_kind(kind)
+                && tokens.get(index + 1).is_some_and(|next| {
+                    next.kind == TokenKind::Punctuation(PunctuationKind::OpenParen)
+                })
+        }
+        _ => false,
+    }
+}
+
+fn rebase_token<'a>(token: &Token<'_>, text: &'a str, delta: isize) -> Token<'a> {
+    let span = shift_range(&token.span, delta);
+    let source = Cow::Borrowed(&text[span.clone()]);
+    let kind = match &token.kind {
+        TokenKind::Keyword(kind) => TokenKind::Keyword(*kind),
+        TokenKind::Punctuation(kind) => TokenKind::Punctuation(*kind),
+        TokenKind::Literal(kind) => TokenKind::Literal(rebase_literal(kind, source)),
+        TokenKind::Identifier(_) => TokenKind::Identifier(source),
+        TokenKind::Directive(_) => TokenKind::Directive(source),
+        TokenKind::Bad(_) => TokenKind::Bad(source),
+        TokenKind::EndOfFile => TokenKind::EndOfFile,
+    };
+
+    Token {
+        kind,
+        span,
+        trivia_span: shift_range(&token.trivia_span, delta),
+        leading_trivia: rebase_trivia(&token.leading_trivia, delta),
+        trailing_trivia: rebase_trivia(&token.trailing_trivia, delta),
+    }
+}
+
+fn rebase_literal<'a>(kind: &LiteralKind<'_>, source: Cow<'a, str>) -> LiteralKind<'a> {
+    match kind {
+        LiteralKind::Boolean(_) => LiteralKind::Boolean(source),
+        LiteralKind::DateTime(_) => LiteralKind::DateTime(source),
+        LiteralKind::Decimal(_) => LiteralKind::Decimal(source),
+        LiteralKind::Guid(_) => LiteralKind::Guid(source),
+        LiteralKind::Int(_) => LiteralKind::Int(source),
+        LiteralKind::Long(_) => LiteralKind::Long(source),
+        LiteralKind::RawGuid(_) => LiteralKind::RawGuid(source),
+        LiteralKind::Real(_) => LiteralKind::Real(source),
+        LiteralKind::Timespan(_) => LiteralKind::Timespan(source),
+        LiteralKind::String(_) => LiteralKind::String(source),
+    }
+}
+
+fn rebase_trivia(trivia: &[Trivia], delta: isize) -> Vec<Trivia> {
+    trivia
+        .iter()
+        .map(|piece| Trivia {
+            kind: piece.kind,
+            span: shift_range(&piece.span, delta),
+        })
+        .collect()
+}
+
+fn shift_range(range: &Range<usize>, delta: isize) -> Range<usize> {
+    range.start.saturating_add_signed(delta)..range.end.saturating_add_signed(delta)
+}
@@ -0,0 +1,124 @@
+use super::KeywordKind;
+
+const NO_NODE: u16 = 0;
+
+#[derive(Clone, Copy)]
+struct TrieNode {
+    byte: u8,
+    first_child: u16,
+    next_sibling: u16,
+    keyword: Option<KeywordKind>,
+}
+
+const EMPTY_NODE: TrieNode = TrieNode {
+    byte: 0,
+    first_child: NO_NODE,
+    next_sibling: NO_NODE,
+    keyword: None,
+};
+
+/// Byte trie over the keyword table, built during constant evaluation.
+///
+/// The root children are indexed directly by their first byte, deeper levels are stored as
+/// first-child/next-sibling lists. Node 0 is the root, so it doubles as the "no node" marker.
+pub(crate) struct KeywordTrie<const N: usize> {
+    root: [u16; 256],
+    nodes: [TrieNode; N],
+}
+
+impl<const N: usize> KeywordTrie<N> {
+    pub(crate) const fn new(keywords: &[(&[u8], KeywordKind)]) -> Self {
+        assert!(
+            N <= u16::MAX as usize,
+            "keyword trie capacity exceeds u16 indices"
+        );
+
+        let mut trie = Self {
+            root: [NO_NODE; 256],
+            nodes: [EMPTY_NODE; N],
+        };
+        let mut len = 1;
+        let mut k = 0;
+
+        while k < keywords.len() {
+            let (keyword, kind) = keywords[k];
+            assert!(!keyword.is_empty(), "keywords must not be empty");
+
+            let mut node = trie.root[keyword[0] as usize];
+            if node == NO_NODE {
+                node = len as u16;
+                trie.nodes[len].byte = keyword[0];
+                trie.root[keyword[0] as usize] = node;
+                len += 1;
+            }
+
+            let mut i = 1;
+            while i < keyword.len() {
+                let byte = keyword[i];
+                let mut child = trie.nodes[node as usize].first_child;
+
+                while child != NO_NODE && trie.nodes[child as usize].byte != byte {
+                    child = trie.nodes[child as usize].next_sibling;
+                }
+
+                if child == NO_NODE {
+                    child = len as u16;
+                    trie.nodes[len].byte = byte;
+                    trie.nodes[len].next_sibling = trie.nodes[node as usize].first_child;
+                    trie.nodes[node as usize].first_child = child;
+                    len += 1;
+                }
+
+                node = child;
+                i += 1;
+            }
+
+            assert!(
+                trie.nodes[node as usize].keyword.is_none(),
+                "duplicate keyword"
+            );
+            trie.nodes[node as usize].keyword = Some(kind);
+            k += 1;
+        }
+
+        trie
+    }
+
+    #[inline]
+    pub(crate) fn longest_match(&self, bytes: &[u8], start: usize) -> Option<(usize, KeywordKind)> {
+        let mut node = self.root[*bytes.get(start)? as usize];
+        let mut pos = start + 1;
+        let mut longest = None;
+
+        while node != NO_NODE {
+            let current = &self.nodes[node as usize];
+            if let Some(kind) = current.keyword {
+                longest = Some((pos - start, kind));
+            }
+
+            let Some(&byte) = bytes.get(pos) else {
+                break;
+            };
+
+            node = current.first_child;
+            while node != NO_NODE && self.nodes[node as usize].byte != byte {
+                node = self.nodes[node as usize].next_sibling;
+            }
+            pos += 1;
+        }
+
+        longest
+    }
+}
+
+pub(crate) const fn keyword_bytes_len(keywords: &[(&[u8], KeywordKind)]) -> usize {
+    let mut len = 0;
+    let mut k = 0;
+
+    while k < keywords.len() {
+        len += keywords[k].0.len();
+        k += 1;
+    }
+
+    len
+}
@@ -1,4 +1,6 @@
 mod constants;
+mod incremental;
+mod keyword_trie;
 mod parser;
 mod scanner;
 mod types;
@@ -7,5 +9,10 @@ mod utilities;
 #[cfg(test)]
 mod tests;
 
-pub use parser::parse_tokens;
-pub use types::{KeywordKind, LiteralKind, ParseOptions, PunctuationKind, TokenKind};
+pub(crate) use constants::KEYWORDS;
+pub use incremental::{TextEdit, reparse_tokens, reparse_tokens_with_diagnostics};
+pub use parser::{Tokenizer, parse_tokens, parse_tokens_with_diagnostics};
+pub use types::{
+    KeywordKind, LexDiagnostic, LexDiagnosticKind, LiteralKind, ParseOptions, PunctuationKind,
+    Token, TokenKind, Trivia, TriviaKind,
+};
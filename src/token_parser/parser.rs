@@ -1,51 +1,147 @@
 use super::scanner::*;
 use super::utilities::*;
-use super::{KeywordKind, LiteralKind, ParseOptions, PunctuationKind, TokenKind, constants::*};
-use std::ops::Range;
+use super::{
+    KeywordKind, LexDiagnostic, LexDiagnosticKind, LiteralKind, ParseOptions, PunctuationKind,
+    Token, TokenKind, constants::*,
+};
+use std::{borrow::Cow, iter::FusedIterator, ops::Range};
 
-pub fn parse_tokens(text: &str, options: &ParseOptions) -> Vec<TokenKind> {
-    let bytes = text.as_bytes();
+pub fn parse_tokens<'a>(text: &'a str, options: &ParseOptions) -> Vec<Token<'a>> {
     // Pre-allocate based on estimation
-    let mut tokens = Vec::with_capacity((bytes.len() / AVG_BYTES_PER_TOKEN).max(1));
-    let mut pos = 0;
+    let mut tokens = Vec::with_capacity((text.len() / AVG_BYTES_PER_TOKEN).max(1));
+    tokens.extend(Tokenizer::new(text, options));
+    tokens
+}
 
-    while let Some((kind, len)) = next_token(text, bytes, pos, options) {
-        let is_eof = kind == TokenKind::EndOfFile;
-        pos += len;
-        tokens.push(kind);
+pub fn parse_tokens_with_diagnostics<'a>(
+    text: &'a str,
+    options: &ParseOptions,
+) -> (Vec<Token<'a>>, Vec<LexDiagnostic>) {
+    let mut tokenizer = Tokenizer::new(text, options);
+    let mut tokens = Vec::with_capacity((text.len() / AVG_BYTES_PER_TOKEN).max(1));
+    tokens.extend(tokenizer.by_ref());
+    (tokens, tokenizer.diagnostics)
+}
+
+/// Lazily produces the tokens `parse_tokens` would return.
+#[derive(Debug, Clone)]
+pub struct Tokenizer<'a> {
+    text: &'a str,
+    options: ParseOptions,
+    pos: usize,
+    has_prior_trivia: bool,
+    is_done: bool,
+    diagnostics: Vec<LexDiagnostic>,
+}
 
-        if is_eof {
-            break;
+impl<'a> Tokenizer<'a> {
+    pub fn new(text: &'a str, options: &ParseOptions) -> Self {
+        Self {
+            text,
+            options: *options,
+            pos: 0,
+            has_prior_trivia: false,
+            is_done: false,
+            diagnostics: Vec::new(),
         }
     }
 
-    tokens
+    /// Diagnostics for the tokens produced so far.
+    pub fn diagnostics(&self) -> &[LexDiagnostic] {
+        &self.diagnostics
+    }
 }
 
-fn next_token(
-    text: &str,
-    bytes: &[u8],
-    start: usize,
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.is_done {
+            return None;
+        }
+
+        let Some(token) = scan_token(
+            self.text,
+            self.pos,
+            self.has_prior_trivia,
+            &self.options,
+            &mut self.diagnostics,
+        ) else {
+            self.is_done = true;
+            return None;
+        };
+
+        self.pos = token.full_span().end;
+        self.has_prior_trivia = !token.trailing_trivia.is_empty();
+        self.is_done = token.kind == TokenKind::EndOfFile;
+        Some(token)
+    }
+}
+
+impl FusedIterator for Tokenizer<'_> {}
+
+/// `has_prior_trivia` tells whether the previous token's trailing trivia ends at `pos`, which
+/// only happens when producing trivia.
+pub(crate) fn scan_token<'a>(
+    text: &'a str,
+    pos: usize,
+    has_prior_trivia: bool,
     options: &ParseOptions,
-) -> Option<(TokenKind, usize)> {
-    let mut pos = start;
-    let trivia_len = scan_trivia(bytes, start).unwrap_or(0);
-    let has_trivia = trivia_len > 0;
+    diagnostics: &mut Vec<LexDiagnostic>,
+) -> Option<Token<'a>> {
+    let bytes = text.as_bytes();
+    let mut leading_trivia = Vec::new();
+    let trivia_len = if options.produce_trivia {
+        scan_trivia_pieces(bytes, pos, false, &mut leading_trivia)
+    } else {
+        scan_trivia(bytes, pos).unwrap_or(0)
+    };
+    let token_start = pos + trivia_len;
+
+    let (kind, len) = next_token(
+        text,
+        bytes,
+        token_start,
+        has_prior_trivia || trivia_len > 0,
+        options,
+        diagnostics,
+    )?;
+    let token_end = token_start + len;
+    let mut trailing_trivia = Vec::new();
+
+    if options.produce_trivia && kind != TokenKind::EndOfFile {
+        scan_trivia_pieces(bytes, token_end, true, &mut trailing_trivia);
+    }
 
-    pos += trivia_len;
+    Some(Token {
+        kind,
+        span: token_start..token_end,
+        trivia_span: pos..token_start,
+        leading_trivia,
+        trailing_trivia,
+    })
+}
 
+fn next_token<'a>(
+    text: &'a str,
+    bytes: &[u8],
+    pos: usize,
+    has_trivia: bool,
+    options: &ParseOptions,
+    diagnostics: &mut Vec<LexDiagnostic>,
+) -> Option<(TokenKind<'a>, usize)> {
     if let Some(&byte) = peek(bytes, pos) {
         if !byte.is_ascii_alphanumeric() {
             if let Some((kind, punc_len)) = parse_punctuation(bytes, pos) {
-                return Some((TokenKind::Punctuation(kind), trivia_len + punc_len));
+                return Some((TokenKind::Punctuation(kind), punc_len));
             }
 
             if is_string_literal_start_quote(byte) {
                 if let Some(text_span) = parse_string_literal(bytes, pos) {
                     let text_len = text_span.end - text_span.start;
                     return Some((
-                        TokenKind::Literal(LiteralKind::String(text[text_span].to_string())),
-                        trivia_len + text_len,
+                        TokenKind::Literal(LiteralKind::String(text[text_span].into())),
+                        text_len,
                     ));
                 }
             } else if byte == b'@'
@@ -55,21 +151,18 @@ fn next_token(
                 if let Some(text_span) = parse_string_literal(bytes, pos) {
                     let text_len = text_span.end - text_span.start;
                     return Some((
-                        TokenKind::Literal(LiteralKind::String(text[text_span].to_string())),
-                        trivia_len + text_len,
+                        TokenKind::Literal(LiteralKind::String(text[text_span].into())),
+                        text_len,
                     ));
                 }
             } else if byte == b'#' {
                 let directive_end = get_line_end(bytes, pos);
                 let text_span = pos..directive_end;
                 let text_len = text_span.end - text_span.start;
-                return Some((
-                    TokenKind::Directive(text[text_span].to_string()),
-                    trivia_len + text_len,
-                ));
+                return Some((TokenKind::Directive(text[text_span].into()), text_len));
             } else if is_at_end(bytes, pos) {
                 if has_trivia || options.always_produce_end_tokens {
-                    return Some((TokenKind::EndOfFile, trivia_len));
+                    return Some((TokenKind::EndOfFile, 0));
                 }
 
                 return None;
@@ -83,51 +176,60 @@ fn next_token(
                 && let Some(goo_len) = scan_goo(bytes, pos + keyword_len, options)
             {
                 let text_span = pos..pos + goo_len + keyword_len;
-                let text = text[text_span].to_string();
+                let text = Cow::Borrowed(&text[text_span]);
                 return match keyword_kind {
                     KeywordKind::Bool => Some((
                         TokenKind::Literal(LiteralKind::Boolean(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::DateTime | KeywordKind::Date => Some((
                         TokenKind::Literal(LiteralKind::DateTime(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::Decimal => Some((
                         TokenKind::Literal(LiteralKind::Decimal(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::Guid => Some((
                         TokenKind::Literal(LiteralKind::Guid(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::Int | KeywordKind::Int32 => Some((
                         TokenKind::Literal(LiteralKind::Int(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::Long | KeywordKind::Int64 => Some((
                         TokenKind::Literal(LiteralKind::Long(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::Real | KeywordKind::Double => Some((
                         TokenKind::Literal(LiteralKind::Real(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     KeywordKind::Time | KeywordKind::Timespan => Some((
                         TokenKind::Literal(LiteralKind::Timespan(text)),
-                        trivia_len + goo_len + keyword_len,
+                        goo_len + keyword_len,
                     )),
                     _ => None,
                 };
             }
 
+            if peek(bytes, pos + keyword_len) == Some(&b'(') && is_goo_literal_kind(&keyword_kind) {
+                let keyword = &text[pos..pos + keyword_len];
+                diagnostics.push(LexDiagnostic {
+                    kind: LexDiagnosticKind::UnterminatedLiteral,
+                    span: pos..pos + keyword_len + 1,
+                    message: format!("unterminated {keyword} literal, expected ')'"),
+                });
+            }
+
             let is_keyword = match peek(bytes, pos + keyword_len) {
                 Some(&next_byte) => !is_identifier_char(next_byte),
                 None => true,
             };
 
             if is_keyword {
-                return Some((TokenKind::Keyword(keyword_kind), trivia_len + keyword_len));
+                return Some((TokenKind::Keyword(keyword_kind), keyword_len));
             }
         }
 
@@ -140,10 +242,8 @@ fn next_token(
 
                 if is_bool {
                     return Some((
-                        TokenKind::Literal(LiteralKind::Boolean(
-                            text[pos..pos + bool_len].to_string(),
-                        )),
-                        trivia_len + bool_len,
+                        TokenKind::Literal(LiteralKind::Boolean(text[pos..pos + bool_len].into())),
+                        bool_len,
                     ));
                 }
             }
@@ -151,8 +251,8 @@ fn next_token(
             if let Some(raw_guid_len) = scan_raw_guid_literal(bytes, pos) {
                 let text_span = pos..pos + raw_guid_len;
                 return Some((
-                    TokenKind::Literal(LiteralKind::RawGuid(text[text_span].to_string())),
-                    trivia_len + raw_guid_len,
+                    TokenKind::Literal(LiteralKind::RawGuid(text[text_span].into())),
+                    raw_guid_len,
                 ));
             }
 
@@ -165,86 +265,102 @@ fn next_token(
                 {
                     let text_len = text_span.end - text_span.start;
                     return Some((
-                        TokenKind::Literal(LiteralKind::String(text[text_span].to_string())),
-                        trivia_len + id_len + text_len,
+                        TokenKind::Literal(LiteralKind::String(text[text_span].into())),
+                        text_len,
                     ));
                 }
 
                 let text_span = pos..pos + id_len;
-                return Some((
-                    TokenKind::Identifier(text[text_span].to_string()),
-                    trivia_len + id_len,
-                ));
+                return Some((TokenKind::Identifier(text[text_span].into()), id_len));
             }
         } else if byte.is_ascii_digit() {
             if let Some(raw_guid_len) = scan_raw_guid_literal(bytes, pos) {
                 let text_span = pos..pos + raw_guid_len;
                 return Some((
-                    TokenKind::Literal(LiteralKind::RawGuid(text[text_span].to_string())),
-                    trivia_len + raw_guid_len,
+                    TokenKind::Literal(LiteralKind::RawGuid(text[text_span].into())),
+                    raw_guid_len,
                 ));
             }
             if let Some(real_len) = scan_real_literal(bytes, pos) {
                 let text_span = pos..pos + real_len;
                 return Some((
-                    TokenKind::Literal(LiteralKind::Real(text[text_span].to_string())),
-                    trivia_len + real_len,
+                    TokenKind::Literal(LiteralKind::Real(text[text_span].into())),
+                    real_len,
                 ));
             }
             if let Some(timespan_len) = scan_timespan_literal(bytes, pos) {
                 let text_span = pos..pos + timespan_len;
                 return Some((
-                    TokenKind::Literal(LiteralKind::Timespan(text[text_span].to_string())),
-                    trivia_len + timespan_len,
+                    TokenKind::Literal(LiteralKind::Timespan(text[text_span].into())),
+                    timespan_len,
                 ));
             }
             if let Some(long_len) = scan_long_literal(bytes, pos) {
                 let text_span = pos..pos + long_len;
                 return Some((
-                    TokenKind::Literal(LiteralKind::Long(text[text_span].to_string())),
-                    trivia_len + long_len,
+                    TokenKind::Literal(LiteralKind::Long(text[text_span].into())),
+                    long_len,
                 ));
             }
             if let Some(id_len) = scan_identifier(bytes, pos) {
                 let text_span = pos..pos + id_len;
-                return Some((
-                    TokenKind::Identifier(text[text_span].to_string()),
-                    trivia_len + id_len,
-                ));
+                return Some((TokenKind::Identifier(text[text_span].into()), id_len));
             }
         }
     } else {
         if has_trivia || options.always_produce_end_tokens {
-            return Some((TokenKind::EndOfFile, trivia_len));
+            return Some((TokenKind::EndOfFile, 0));
         }
         return None;
     }
 
-    // Get the length of the UTF-8 character at pos to avoid splitting multi-byte characters
-    // Use UTF-8 leading byte pattern to determine character length
-    let char_len = if !is_at_end(bytes, pos) {
-        let byte = bytes[pos];
-        if byte & UTF8_1_BYTE_MASK == UTF8_1_BYTE_PATTERN {
-            1 // ASCII (0xxxxxxx)
-        } else if byte & UTF8_2_BYTE_MASK == UTF8_2_BYTE_PATTERN {
-            2 // 2-byte (110xxxxx)
-        } else if byte & UTF8_3_BYTE_MASK == UTF8_3_BYTE_PATTERN {
-            3 // 3-byte (1110xxxx)
-        } else if byte & UTF8_4_BYTE_MASK == UTF8_4_BYTE_PATTERN {
-            4 // 4-byte (11110xxx)
-        } else {
-            1 // Invalid UTF-8, treat as single byte
-        }
-    } else {
-        1
-    };
+    let char_len = get_char_len(bytes, pos);
+
+    // Pieces of an already reported string opener or escape are not reported again
+    if !diagnostics.last().is_some_and(|d| d.span.contains(&pos)) {
+        diagnostics.push(get_bad_token_diagnostic(text, pos, char_len));
+    }
 
     let text_span = pos..pos + char_len;
     let text_len = text_span.end - text_span.start;
-    Some((
-        TokenKind::Bad(text[text_span].to_string()),
-        trivia_len + text_len,
-    ))
+    Some((TokenKind::Bad(text[text_span].into()), text_len))
+}
+
+fn get_bad_token_diagnostic(text: &str, pos: usize, char_len: usize) -> LexDiagnostic {
+    let bytes = text.as_bytes();
+
+    if let Some((kind, span)) = scan_string_literal_error(bytes, pos) {
+        let message = match kind {
+            LexDiagnosticKind::InvalidEscape => {
+                format!("invalid escape sequence '{}'", &text[span.clone()])
+            }
+            LexDiagnosticKind::UnterminatedMultilineString => format!(
+                "unterminated multi-line string literal, expected closing '{}'",
+                &text[span.clone()]
+            ),
+            _ => "unterminated string literal".to_string(),
+        };
+
+        return LexDiagnostic {
+            kind,
+            span,
+            message,
+        };
+    }
+
+    let char_text = &text[pos..pos + char_len];
+    let message = match char_text.chars().next() {
+        Some(c) if !c.is_ascii() => {
+            format!("invalid character '{c}' (U+{:04X})", c as u32)
+        }
+        _ => format!("unexpected character '{char_text}'"),
+    };
+
+    LexDiagnostic {
+        kind: LexDiagnosticKind::InvalidCharacter,
+        span: pos..pos + char_len,
+        message,
+    }
 }
 
 fn parse_bool_literal(bytes: &[u8], start: usize) -> Option<usize> {
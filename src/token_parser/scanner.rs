@@ -1,6 +1,7 @@
-use crate::token_parser::ParseOptions;
+use crate::token_parser::{LexDiagnosticKind, ParseOptions, Trivia, TriviaKind};
 
-use super::utilities::*;
+use super::{constants::MULTI_LINE_STRING_SEQUENCES, utilities::*};
+use std::ops::Range;
 
 pub(crate) fn scan_trivia(bytes: &[u8], start: usize) -> Option<usize> {
     let mut pos = start;
@@ -31,6 +32,47 @@ pub(crate) fn scan_trivia(bytes: &[u8], start: usize) -> Option<usize> {
     }
 }
 
+pub(crate) fn scan_trivia_pieces(
+    bytes: &[u8],
+    start: usize,
+    stop_after_line_break: bool,
+    pieces: &mut Vec<Trivia>,
+) -> usize {
+    let mut pos = start;
+
+    while let Some(&byte) = peek(bytes, pos) {
+        let (kind, len) = if is_line_break_start(byte) {
+            let len = if byte == b'\r' && peek(bytes, pos + 1) == Some(&b'\n') {
+                2
+            } else {
+                1
+            };
+            (TriviaKind::LineBreak, len)
+        } else if byte.is_ascii_whitespace() {
+            let len = count_while(bytes, pos, |&b| {
+                b.is_ascii_whitespace() && !is_line_break_start(b)
+            });
+            (TriviaKind::Whitespace, len)
+        } else if matches_sequence(bytes, pos, b"//") {
+            (TriviaKind::Comment, get_line_end(bytes, pos) - pos)
+        } else {
+            break;
+        };
+
+        pieces.push(Trivia {
+            kind,
+            span: pos..pos + len,
+        });
+        pos += len;
+
+        if stop_after_line_break && kind == TriviaKind::LineBreak {
+            break;
+        }
+    }
+
+    pos - start
+}
+
 pub(crate) fn scan_goo(bytes: &[u8], start: usize, options: &ParseOptions) -> Option<usize> {
     let byte = *peek(bytes, start)?;
     let mut pos = start;
@@ -88,6 +130,61 @@ pub(crate) fn scan_multi_line_string_literal(bytes: &[u8], start: usize, sequenc
     pos - start
 }
 
+pub(crate) fn scan_string_literal_error(
+    bytes: &[u8],
+    start: usize,
+) -> Option<(LexDiagnosticKind, Range<usize>)> {
+    let mut pos = start;
+
+    if matches!(peek(bytes, pos), Some(b'h' | b'H')) {
+        pos += 1;
+    }
+
+    let is_verbatim = peek(bytes, pos) == Some(&b'@');
+    if is_verbatim {
+        pos += 1;
+    }
+
+    let quote_byte = *peek(bytes, pos)?;
+    if quote_byte != b'\'' && quote_byte != b'"' {
+        return MULTI_LINE_STRING_SEQUENCES
+            .iter()
+            .find(|sequence| matches_sequence(bytes, start, sequence))
+            .map(|sequence| {
+                (
+                    LexDiagnosticKind::UnterminatedMultilineString,
+                    start..start + sequence.len(),
+                )
+            });
+    }
+    pos += 1;
+
+    while let Some(&byte) = peek(bytes, pos) {
+        if byte == quote_byte && is_verbatim && peek(bytes, pos + 1) == Some(&quote_byte) {
+            pos += 2;
+        } else if byte == b'\\' && !is_verbatim {
+            let Some(escape_len) = scan_string_escape(bytes, pos) else {
+                let escape_end = match peek(bytes, pos + 1) {
+                    Some(&next_byte) if !is_line_break_start(next_byte) => {
+                        pos + 1 + get_char_len(bytes, pos + 1)
+                    }
+                    _ => pos + 1,
+                };
+                return Some((LexDiagnosticKind::InvalidEscape, pos..escape_end));
+            };
+            pos += escape_len;
+        } else if byte == quote_byte {
+            return None;
+        } else if is_line_break_start(byte) {
+            break;
+        } else {
+            pos += 1;
+        }
+    }
+
+    Some((LexDiagnosticKind::UnterminatedString, start..pos))
+}
+
 pub(crate) fn scan_identifier(bytes: &[u8], start: usize) -> Option<usize> {
     let mut pos = start;
 
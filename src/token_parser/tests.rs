@@ -1,5 +1,10 @@
+use std::{borrow::Cow, ops::Range};
+
+use super::{constants::KEYWORDS, utilities::get_longest_keyword};
 use crate::token_parser::{
-    KeywordKind, LiteralKind, ParseOptions, PunctuationKind, TokenKind, parse_tokens,
+    KeywordKind, LexDiagnostic, LexDiagnosticKind, LiteralKind, ParseOptions, PunctuationKind,
+    TextEdit, Token, TokenKind, Tokenizer, Trivia, TriviaKind, parse_tokens,
+    parse_tokens_with_diagnostics, reparse_tokens, reparse_tokens_with_diagnostics,
 };
 
 #[test]
@@ -9,7 +14,7 @@ fn test_empty_string() {
     let tokens = parse_tokens(input, &options);
 
     assert_eq!(tokens.len(), 1);
-    assert_eq!(tokens[0], TokenKind::EndOfFile);
+    assert_eq!(tokens[0].kind, TokenKind::EndOfFile);
 }
 
 #[test]
@@ -20,8 +25,11 @@ fn test_single_punctuation() {
 
     // Expect: [+] [EOF]
     assert_eq!(tokens.len(), 2);
-    assert_eq!(tokens[0], TokenKind::Punctuation(PunctuationKind::Plus));
-    assert_eq!(tokens[1], TokenKind::EndOfFile);
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
+    );
+    assert_eq!(tokens[1].kind, TokenKind::EndOfFile);
 }
 
 #[test]
@@ -31,15 +39,21 @@ fn test_multi_char_punctuation() {
     let tokens = parse_tokens(input, &options);
 
     assert_eq!(
-        tokens[0],
+        tokens[0].kind,
         TokenKind::Punctuation(PunctuationKind::LessThanOrEqual)
     );
     assert_eq!(
-        tokens[1],
+        tokens[1].kind,
         TokenKind::Punctuation(PunctuationKind::EqualEqual)
     );
-    assert_eq!(tokens[2], TokenKind::Punctuation(PunctuationKind::FatArrow));
-    assert_eq!(tokens[3], TokenKind::Punctuation(PunctuationKind::DotDot));
+    assert_eq!(
+        tokens[2].kind,
+        TokenKind::Punctuation(PunctuationKind::FatArrow)
+    );
+    assert_eq!(
+        tokens[3].kind,
+        TokenKind::Punctuation(PunctuationKind::DotDot)
+    );
 }
 
 #[test]
@@ -48,8 +62,11 @@ fn test_trivia_and_comments() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Punctuation(PunctuationKind::Plus));
-    assert_eq!(tokens[1], TokenKind::EndOfFile);
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
+    );
+    assert_eq!(tokens[1].kind, TokenKind::EndOfFile);
 }
 
 #[test]
@@ -58,7 +75,7 @@ fn test_bad_token() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Bad("ا".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Bad("ا".into()));
 }
 
 #[test]
@@ -67,7 +84,7 @@ fn test_bad_token_utf8_2_byte() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Bad("¿".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Bad("¿".into()));
 }
 
 #[test]
@@ -76,7 +93,7 @@ fn test_bad_token_utf8_3_byte() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Bad("€".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Bad("€".into()));
 }
 
 #[test]
@@ -85,7 +102,7 @@ fn test_bad_token_utf8_4_byte() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Bad("𝕏".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Bad("𝕏".into()));
 }
 
 #[test]
@@ -95,9 +112,9 @@ fn test_multiple_consecutive_bad_tokens() {
     let tokens = parse_tokens(input, &options);
 
     assert_eq!(tokens.len(), 3);
-    assert_eq!(tokens[0], TokenKind::Bad("¿".to_string()));
-    assert_eq!(tokens[1], TokenKind::Bad("€".to_string()));
-    assert_eq!(tokens[2], TokenKind::Bad("𝕏".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Bad("¿".into()));
+    assert_eq!(tokens[1].kind, TokenKind::Bad("€".into()));
+    assert_eq!(tokens[2].kind, TokenKind::Bad("𝕏".into()));
 }
 
 #[test]
@@ -108,9 +125,12 @@ fn test_bad_token_mixed_with_valid_tokens() {
 
     assert_eq!(tokens.len(), 3);
 
-    assert_eq!(tokens[0], TokenKind::Bad("¿".to_string()));
-    assert_eq!(tokens[1], TokenKind::Punctuation(PunctuationKind::Plus));
-    assert_eq!(tokens[2], TokenKind::Bad("€".to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Bad("¿".into()));
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
+    );
+    assert_eq!(tokens[2].kind, TokenKind::Bad("€".into()));
 }
 
 #[test]
@@ -121,20 +141,26 @@ fn test_complex_punctuation_chain() {
 
     assert_eq!(tokens.len(), 6);
     assert_eq!(
-        tokens[0],
+        tokens[0].kind,
         TokenKind::Punctuation(PunctuationKind::BangEqual)
     );
     assert_eq!(
-        tokens[1],
+        tokens[1].kind,
         TokenKind::Punctuation(PunctuationKind::BangTilde)
     );
     assert_eq!(
-        tokens[2],
+        tokens[2].kind,
         TokenKind::Punctuation(PunctuationKind::LessThanBar)
     );
-    assert_eq!(tokens[3], TokenKind::Punctuation(PunctuationKind::LessThan));
-    assert_eq!(tokens[4], TokenKind::Punctuation(PunctuationKind::Question));
-    assert_eq!(tokens[5], TokenKind::EndOfFile);
+    assert_eq!(
+        tokens[3].kind,
+        TokenKind::Punctuation(PunctuationKind::LessThan)
+    );
+    assert_eq!(
+        tokens[4].kind,
+        TokenKind::Punctuation(PunctuationKind::Question)
+    );
+    assert_eq!(tokens[5].kind, TokenKind::EndOfFile);
 }
 
 #[test]
@@ -144,7 +170,10 @@ fn test_options_no_end_tokens() {
     let tokens = parse_tokens(input, &options);
 
     assert_eq!(tokens.len(), 1);
-    assert_eq!(tokens[0], TokenKind::Punctuation(PunctuationKind::Plus));
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
+    );
 }
 
 #[test]
@@ -198,9 +227,9 @@ fn test_all_possible_punctuations() {
 
     for (i, expected_kind) in expected_kinds.iter().enumerate() {
         assert_eq!(
-            tokens[i], *expected_kind,
+            tokens[i].kind, *expected_kind,
             "Mismatch at index {}: expected {:?}, but found {:?}",
-            i, expected_kind, tokens[i]
+            i, expected_kind, tokens[i].kind
         );
     }
 }
@@ -212,7 +241,7 @@ fn test_directive() {
     let tokens = parse_tokens(input, &options);
 
     assert_eq!(tokens.len(), 1);
-    assert_eq!(tokens[0], TokenKind::Directive(input.to_string()));
+    assert_eq!(tokens[0].kind, TokenKind::Directive(input.into()));
 }
 
 #[test]
@@ -222,12 +251,18 @@ fn test_directive_with_other_tokens() {
     let tokens = parse_tokens(input, &options);
 
     assert_eq!(tokens.len(), 3);
-    assert_eq!(tokens[0], TokenKind::Punctuation(PunctuationKind::Plus));
     assert_eq!(
-        tokens[1],
-        TokenKind::Directive("#crp query_language=kql".to_string())
+        tokens[0].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
+    );
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::Directive("#crp query_language=kql".into())
+    );
+    assert_eq!(
+        tokens[2].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
     );
-    assert_eq!(tokens[2], TokenKind::Punctuation(PunctuationKind::Plus));
 }
 
 #[test]
@@ -242,7 +277,7 @@ fn test_identifier() {
         let tokens = parse_tokens(input, &options);
 
         assert_eq!(tokens.len(), 1, "{input}");
-        assert_eq!(tokens[0], TokenKind::Identifier(input.to_string()));
+        assert_eq!(tokens[0].kind, TokenKind::Identifier(input.into()));
     }
 }
 
@@ -260,8 +295,8 @@ fn test_raw_guid_literal() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::RawGuid(input.to_string()))
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::RawGuid(input.into()))
         );
     }
 }
@@ -279,8 +314,8 @@ fn test_real_literal() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::Real(input.to_string()))
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Real(input.into()))
         );
     }
 }
@@ -315,6 +350,7 @@ fn test_timespan_literal() {
         "700sec",
         "800min",
         "900day",
+        "950days",
         "1000ms",
         "1100hr",
         "1200d",
@@ -323,6 +359,7 @@ fn test_timespan_literal() {
         "1500s",
         // with fractional part
         "1.5seconds",
+        "3.5days",
     ];
 
     for input in possible_inputs {
@@ -331,8 +368,8 @@ fn test_timespan_literal() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::Timespan(input.to_string()))
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Timespan(input.into()))
         );
     }
 }
@@ -353,8 +390,8 @@ fn test_long_literal() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::Long(input.to_string()))
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Long(input.into()))
         );
     }
 }
@@ -397,8 +434,8 @@ fn test_string_literal() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::String(input.to_string()))
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::String(input.into()))
         );
     }
 }
@@ -419,8 +456,8 @@ fn test_unclosed_string() {
         let tokens = parse_tokens(input, &options);
 
         assert_ne!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::String(input.to_string())),
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::String(input.into())),
             "{input}"
         );
     }
@@ -439,8 +476,8 @@ fn test_string_with_invalid_escape() {
         let tokens = parse_tokens(input, &options);
 
         assert_ne!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::String(input.to_string())),
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::String(input.into())),
             "{input}"
         );
     }
@@ -453,8 +490,8 @@ fn test_escape_at_eof() {
     let tokens = parse_tokens(input, &options);
 
     assert_ne!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::String(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::String(input.into()))
     );
 }
 
@@ -474,8 +511,8 @@ fn test_string_with_valid_escape_sequences() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::String(input.to_string())),
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::String(input.into())),
             "{input}"
         );
     }
@@ -495,8 +532,8 @@ fn test_verbatim_string_escaping() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::String(input.to_string())),
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::String(input.into())),
             "{input}"
         );
     }
@@ -510,8 +547,8 @@ fn test_string_terminates_at_newline() {
 
     // String should terminate at newline, making it invalid
     assert_ne!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::String(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::String(input.into()))
     );
 }
 
@@ -525,8 +562,8 @@ fn test_bool_literal() {
 
         assert_eq!(tokens.len(), 1, "{input}");
         assert_eq!(
-            tokens[0],
-            TokenKind::Literal(LiteralKind::Boolean(input.to_string()))
+            tokens[0].kind,
+            TokenKind::Literal(LiteralKind::Boolean(input.into()))
         );
     }
 }
@@ -553,7 +590,10 @@ fn test_simple_keywords() {
         let tokens = parse_tokens(input, &options);
 
         assert_eq!(tokens.len(), 1, "Failed for keyword: {input}");
-        assert_eq!(tokens[0], expected_kind, "Wrong kind for keyword: {input}");
+        assert_eq!(
+            tokens[0].kind, expected_kind,
+            "Wrong kind for keyword: {input}"
+        );
     }
 }
 
@@ -579,7 +619,7 @@ fn test_type_keywords() {
 
         assert_eq!(tokens.len(), 1, "Failed for type keyword: {input}");
         assert_eq!(
-            tokens[0], expected_kind,
+            tokens[0].kind, expected_kind,
             "Wrong kind for type keyword: {input}"
         );
     }
@@ -611,7 +651,10 @@ fn test_long_keywords() {
         let tokens = parse_tokens(input, &options);
 
         assert_eq!(tokens.len(), 1, "Failed for keyword: {input}");
-        assert_eq!(tokens[0], expected_kind, "Wrong kind for keyword: {input}");
+        assert_eq!(
+            tokens[0].kind, expected_kind,
+            "Wrong kind for keyword: {input}"
+        );
     }
 }
 
@@ -638,7 +681,10 @@ fn test_keyword_with_special_chars() {
         let tokens = parse_tokens(input, &options);
 
         assert_eq!(tokens.len(), 1, "Failed for keyword: {input}");
-        assert_eq!(tokens[0], expected_kind, "Wrong kind for keyword: {input}");
+        assert_eq!(
+            tokens[0].kind, expected_kind,
+            "Wrong kind for keyword: {input}"
+        );
     }
 }
 
@@ -646,10 +692,10 @@ fn test_keyword_with_special_chars() {
 fn test_keyword_boundary_detection() {
     // Keywords should not match if followed by identifier characters
     let test_cases = vec![
-        ("letx", TokenKind::Identifier("letx".to_string())),
-        ("where_col", TokenKind::Identifier("where_col".to_string())),
+        ("letx", TokenKind::Identifier("letx".into())),
+        ("where_col", TokenKind::Identifier("where_col".into())),
         ("int32", TokenKind::Keyword(KeywordKind::Int32)), // This is actually a different keyword
-        ("datetime2", TokenKind::Identifier("datetime2".to_string())),
+        ("datetime2", TokenKind::Identifier("datetime2".into())),
     ];
 
     for (input, expected_kind) in test_cases {
@@ -657,7 +703,10 @@ fn test_keyword_boundary_detection() {
         let tokens = parse_tokens(input, &options);
 
         assert_eq!(tokens.len(), 1, "Failed for input: {input}");
-        assert_eq!(tokens[0], expected_kind, "Wrong kind for input: {input}");
+        assert_eq!(
+            tokens[0].kind, expected_kind,
+            "Wrong kind for input: {input}"
+        );
     }
 }
 
@@ -667,12 +716,15 @@ fn test_keyword_followed_by_punctuation() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Keyword(KeywordKind::Let));
-    assert_eq!(tokens[1], TokenKind::Identifier("x".to_string()));
-    assert_eq!(tokens[2], TokenKind::Punctuation(PunctuationKind::Equal));
+    assert_eq!(tokens[0].kind, TokenKind::Keyword(KeywordKind::Let));
+    assert_eq!(tokens[1].kind, TokenKind::Identifier("x".into()));
+    assert_eq!(
+        tokens[2].kind,
+        TokenKind::Punctuation(PunctuationKind::Equal)
+    );
     assert_eq!(
-        tokens[3],
-        TokenKind::Literal(LiteralKind::Long("5".to_string()))
+        tokens[3].kind,
+        TokenKind::Literal(LiteralKind::Long("5".into()))
     );
 }
 
@@ -694,7 +746,10 @@ fn test_longest_keyword_match() {
         let tokens = parse_tokens(input, &options);
 
         assert_eq!(tokens.len(), 1, "Failed for input: {input}");
-        assert_eq!(tokens[0], expected_kind, "Wrong kind for input: {input}");
+        assert_eq!(
+            tokens[0].kind, expected_kind,
+            "Wrong kind for input: {input}"
+        );
     }
 }
 
@@ -708,8 +763,8 @@ fn test_goo_int_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Int(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Int(input.into()))
     );
 }
 
@@ -721,8 +776,8 @@ fn test_goo_long_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Long(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Long(input.into()))
     );
 }
 
@@ -734,8 +789,8 @@ fn test_goo_datetime_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::DateTime(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::DateTime(input.into()))
     );
 }
 
@@ -747,8 +802,8 @@ fn test_goo_timespan_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Timespan(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Timespan(input.into()))
     );
 }
 
@@ -760,8 +815,8 @@ fn test_goo_real_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Real(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Real(input.into()))
     );
 }
 
@@ -773,8 +828,8 @@ fn test_goo_decimal_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Decimal(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Decimal(input.into()))
     );
 }
 
@@ -786,8 +841,8 @@ fn test_goo_guid_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Guid(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Guid(input.into()))
     );
 }
 
@@ -799,8 +854,8 @@ fn test_goo_bool_literal() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Boolean(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Boolean(input.into()))
     );
 }
 
@@ -812,8 +867,8 @@ fn test_goo_with_whitespace() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Int(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Int(input.into()))
     );
 }
 
@@ -825,7 +880,7 @@ fn test_goo_unclosed_paren() {
 
     // Should parse as keyword followed by open paren and number
     assert!(tokens.len() > 1);
-    assert_eq!(tokens[0], TokenKind::Keyword(KeywordKind::Int));
+    assert_eq!(tokens[0].kind, TokenKind::Keyword(KeywordKind::Int));
 }
 
 #[test]
@@ -836,7 +891,7 @@ fn test_goo_with_line_breaks_not_allowed() {
 
     // Should not parse as goo literal when line breaks not allowed
     assert!(tokens.len() > 1);
-    assert_eq!(tokens[0], TokenKind::Keyword(KeywordKind::Int));
+    assert_eq!(tokens[0].kind, TokenKind::Keyword(KeywordKind::Int));
 }
 
 #[test]
@@ -850,8 +905,8 @@ fn test_goo_with_line_breaks_allowed() {
     // Should parse as goo literal when line breaks allowed
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Int(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Int(input.into()))
     );
 }
 
@@ -861,11 +916,14 @@ fn test_type_keyword_not_followed_by_paren() {
     let options = ParseOptions::default().with_always_produce_end_tokens(false);
     let tokens = parse_tokens(input, &options);
 
-    assert_eq!(tokens[0], TokenKind::Keyword(KeywordKind::Int));
-    assert_eq!(tokens[1], TokenKind::Punctuation(PunctuationKind::Plus));
+    assert_eq!(tokens[0].kind, TokenKind::Keyword(KeywordKind::Int));
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::Punctuation(PunctuationKind::Plus)
+    );
     assert_eq!(
-        tokens[2],
-        TokenKind::Literal(LiteralKind::Long("5".to_string()))
+        tokens[2].kind,
+        TokenKind::Literal(LiteralKind::Long("5".into()))
     );
 }
 
@@ -878,8 +936,8 @@ fn test_date_keyword_goo() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::DateTime(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::DateTime(input.into()))
     );
 }
 
@@ -892,8 +950,8 @@ fn test_time_keyword_goo() {
 
     assert_eq!(tokens.len(), 1);
     assert_eq!(
-        tokens[0],
-        TokenKind::Literal(LiteralKind::Timespan(input.to_string()))
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::Timespan(input.into()))
     );
 }
 
@@ -904,21 +962,21 @@ fn test_complex_query_with_keywords() {
     let tokens = parse_tokens(input, &options);
 
     // Verify key tokens are present
-    assert_eq!(tokens[0], TokenKind::Keyword(KeywordKind::Let));
+    assert_eq!(tokens[0].kind, TokenKind::Keyword(KeywordKind::Let));
     assert!(
         tokens
             .iter()
-            .any(|t| *t == TokenKind::Keyword(KeywordKind::Where))
+            .any(|t| t.kind == TokenKind::Keyword(KeywordKind::Where))
     );
     assert!(
         tokens
             .iter()
-            .any(|t| *t == TokenKind::Keyword(KeywordKind::Project))
+            .any(|t| t.kind == TokenKind::Keyword(KeywordKind::Project))
     );
     assert!(
         tokens
             .iter()
-            .any(|t| *t == TokenKind::Keyword(KeywordKind::Summarize))
+            .any(|t| t.kind == TokenKind::Keyword(KeywordKind::Summarize))
     );
 }
 
@@ -949,8 +1007,734 @@ fn test_hint_keywords() {
 
         assert_eq!(tokens.len(), 1, "Failed for hint keyword: {input}");
         assert_eq!(
-            tokens[0], expected_kind,
+            tokens[0].kind, expected_kind,
             "Wrong kind for hint keyword: {input}"
         );
     }
 }
+
+// ============ Span Tests ============
+
+#[test]
+fn test_token_spans() {
+    let input = "let x = 5";
+    let options = ParseOptions::default();
+    let tokens = parse_tokens(input, &options);
+
+    let spans: Vec<_> = tokens
+        .iter()
+        .map(|t| (t.trivia_span.clone(), t.span.clone()))
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            (0..0, 0..3),
+            (3..4, 4..5),
+            (5..6, 6..7),
+            (7..8, 8..9),
+            (9..9, 9..9)
+        ]
+    );
+}
+
+#[test]
+fn test_token_spans_with_comment_trivia() {
+    let input = "  // comment\n  +  ";
+    let options = ParseOptions::default();
+    let tokens = parse_tokens(input, &options);
+
+    assert_eq!(
+        tokens,
+        vec![
+            Token {
+                kind: TokenKind::Punctuation(PunctuationKind::Plus),
+                span: 15..16,
+                trivia_span: 0..15,
+                leading_trivia: vec![],
+                trailing_trivia: vec![],
+            },
+            Token {
+                kind: TokenKind::EndOfFile,
+                span: 18..18,
+                trivia_span: 16..18,
+                leading_trivia: vec![],
+                trailing_trivia: vec![],
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_token_spans_cover_source_text() {
+    let input = "T | where h'secret' != datetime(2024-01-01) // done\n| take 10";
+    let options = ParseOptions::default();
+    let tokens = parse_tokens(input, &options);
+
+    let mut pos = 0;
+    for token in &tokens {
+        assert_eq!(token.trivia_span.start, pos, "{token:?}");
+        assert_eq!(token.trivia_span.end, token.span.start, "{token:?}");
+        pos = token.span.end;
+    }
+    assert_eq!(pos, input.len());
+
+    let texts: Vec<_> = tokens.iter().map(|t| &input[t.span.clone()]).collect();
+    assert_eq!(
+        texts,
+        vec![
+            "T",
+            "|",
+            "where",
+            "h'secret'",
+            "!=",
+            "datetime(2024-01-01)",
+            "|",
+            "take",
+            "10",
+            ""
+        ]
+    );
+}
+
+#[test]
+fn test_obfuscated_string_followed_by_identifier() {
+    let input = "h'a'x";
+    let options = ParseOptions::default().with_always_produce_end_tokens(false);
+    let tokens = parse_tokens(input, &options);
+
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::Literal(LiteralKind::String("h'a'".into()))
+    );
+    assert_eq!(tokens[1].kind, TokenKind::Identifier("x".into()));
+    assert_eq!(tokens[1].span, 4..5);
+}
+
+// ============ Trivia Tests ============
+
+#[test]
+fn test_trivia_round_trip() {
+    let inputs = vec![
+        "",
+        "   ",
+        "T | where x > 1",
+        "  // leading comment\nT\r\n| take 10 // trailing comment\n\n// final comment",
+        "let x = 1;\n\tlet y = @'a''b';\r\nx + y  ",
+        "print ```multi\nline``` , h'secret'\n#directive\n",
+        "¿ € 𝕏 //",
+    ];
+
+    for input in inputs {
+        for always_produce_end_tokens in [true, false] {
+            let options = ParseOptions::default()
+                .with_always_produce_end_tokens(always_produce_end_tokens)
+                .with_produce_trivia(true);
+            let tokens = parse_tokens(input, &options);
+
+            let mut output = String::new();
+            for token in &tokens {
+                for trivia in &token.leading_trivia {
+                    output.push_str(&input[trivia.span.clone()]);
+                }
+                output.push_str(&input[token.span.clone()]);
+                for trivia in &token.trailing_trivia {
+                    output.push_str(&input[trivia.span.clone()]);
+                }
+            }
+
+            assert_eq!(output, input, "{input:?}");
+        }
+    }
+}
+
+#[test]
+fn test_trivia_mode_produces_same_tokens() {
+    let inputs = [
+        "  +  // comment",
+        "T | take 1 \n",
+        "T | take 1",
+        "x // comment\n  ",
+        "a\tb  ",
+        "  ",
+    ];
+
+    for input in inputs {
+        for always_produce_end_tokens in [true, false] {
+            let options =
+                ParseOptions::default().with_always_produce_end_tokens(always_produce_end_tokens);
+            let kinds_and_spans = |options: &ParseOptions| {
+                parse_tokens(input, options)
+                    .into_iter()
+                    .map(|token| (token.kind, token.span))
+                    .collect::<Vec<_>>()
+            };
+
+            assert_eq!(
+                kinds_and_spans(&options.with_produce_trivia(true)),
+                kinds_and_spans(&options),
+                "{input:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_trivia_kinds() {
+    let input = "a  // note\r\n\t b";
+    let options = ParseOptions::default().with_produce_trivia(true);
+    let tokens = parse_tokens(input, &options);
+
+    assert_eq!(tokens.len(), 3);
+    assert!(tokens[0].leading_trivia.is_empty());
+    assert_eq!(
+        tokens[0].trailing_trivia,
+        vec![
+            Trivia {
+                kind: TriviaKind::Whitespace,
+                span: 1..3,
+            },
+            Trivia {
+                kind: TriviaKind::Comment,
+                span: 3..10,
+            },
+            Trivia {
+                kind: TriviaKind::LineBreak,
+                span: 10..12,
+            },
+        ]
+    );
+    assert_eq!(
+        tokens[1].leading_trivia,
+        vec![Trivia {
+            kind: TriviaKind::Whitespace,
+            span: 12..14,
+        }]
+    );
+    assert_eq!(tokens[1].trivia_span, 12..14);
+    assert_eq!(tokens[1].span, 14..15);
+}
+
+#[test]
+fn test_trailing_trivia_stops_after_line_break() {
+    let input = "a\n\n  b";
+    let options = ParseOptions::default().with_produce_trivia(true);
+    let tokens = parse_tokens(input, &options);
+
+    assert_eq!(
+        tokens[0].trailing_trivia,
+        vec![Trivia {
+            kind: TriviaKind::LineBreak,
+            span: 1..2,
+        }]
+    );
+    assert_eq!(
+        tokens[1].leading_trivia,
+        vec![
+            Trivia {
+                kind: TriviaKind::LineBreak,
+                span: 2..3,
+            },
+            Trivia {
+                kind: TriviaKind::Whitespace,
+                span: 3..5,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_trivia_not_produced_by_default() {
+    let input = " a // note\n b ";
+    let options = ParseOptions::default();
+    let tokens = parse_tokens(input, &options);
+
+    assert!(
+        tokens
+            .iter()
+            .all(|t| t.leading_trivia.is_empty() && t.trailing_trivia.is_empty())
+    );
+    assert_eq!(tokens[1].trivia_span, 2..12);
+}
+
+// ============ Borrowing Tests ============
+
+#[test]
+fn test_tokens_borrow_source_text() {
+    let input = "T | where Name == 'x' and ts > datetime(2024-01-01) ¿";
+    let options = ParseOptions::default();
+    let tokens = parse_tokens(input, &options);
+
+    for token in &tokens {
+        let text = match &token.kind {
+            TokenKind::Identifier(text) | TokenKind::Directive(text) | TokenKind::Bad(text) => text,
+            TokenKind::Literal(LiteralKind::String(text))
+            | TokenKind::Literal(LiteralKind::DateTime(text)) => text,
+            _ => continue,
+        };
+
+        assert!(matches!(text, Cow::Borrowed(_)), "{token:?}");
+        assert_eq!(text.as_ptr(), input[token.span.clone()].as_ptr());
+    }
+}
+
+#[test]
+fn test_into_owned_outlives_source() {
+    let tokens = {
+        let input = String::from("x + 'y'");
+        let options = ParseOptions::default().with_always_produce_end_tokens(false);
+        parse_tokens(&input, &options)
+            .into_iter()
+            .map(Token::into_owned)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(tokens[0].kind, TokenKind::Identifier("x".into()));
+    assert!(matches!(
+        tokens[0].kind,
+        TokenKind::Identifier(Cow::Owned(_))
+    ));
+    assert_eq!(
+        tokens[2].kind,
+        TokenKind::Literal(LiteralKind::String("'y'".into()))
+    );
+    assert_eq!(tokens[2].span, 4..7);
+}
+
+// ============ Keyword Trie Tests ============
+
+fn linear_longest_keyword(bytes: &[u8], start: usize) -> Option<(usize, KeywordKind)> {
+    KEYWORDS
+        .iter()
+        .filter(|(keyword, _)| bytes[start..].starts_with(keyword))
+        .max_by_key(|(keyword, _)| keyword.len())
+        .map(|&(keyword, kind)| (keyword.len(), kind))
+}
+
+#[test]
+fn test_keyword_trie_matches_linear_scan() {
+    for &(keyword, kind) in KEYWORDS {
+        assert_eq!(
+            get_longest_keyword(keyword, 0),
+            Some((keyword.len(), kind)),
+            "{}",
+            String::from_utf8_lossy(keyword)
+        );
+
+        for suffix in [&b"x"[..], b"_", b"-", b".", b"~", b" ", b"(", b"-view"] {
+            let input = [keyword, suffix].concat();
+            assert_eq!(
+                get_longest_keyword(&input, 0),
+                linear_longest_keyword(&input, 0),
+                "{}",
+                String::from_utf8_lossy(&input)
+            );
+        }
+
+        for len in 0..keyword.len() {
+            let input = [&b"  "[..], &keyword[..len]].concat();
+            assert_eq!(
+                get_longest_keyword(&input, 2),
+                linear_longest_keyword(&input, 2),
+                "{}",
+                String::from_utf8_lossy(&input)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_dashed_and_dotted_longest_keyword_match() {
+    let test_cases = vec![
+        (
+            "materialized-view-combine",
+            Some((25, KeywordKind::MaterializedViewCombine)),
+        ),
+        (
+            "materialized-views",
+            Some((18, KeywordKind::MaterializedViews)),
+        ),
+        ("materialized-view", Some((11, KeywordKind::Materialize))),
+        (
+            "hint.num_partitions",
+            Some((19, KeywordKind::HintDotNumPartitions)),
+        ),
+        ("hint.num", None),
+        ("project-reorder", Some((15, KeywordKind::ProjectReorder))),
+        ("project-", Some((7, KeywordKind::Project))),
+        ("!in~", Some((4, KeywordKind::NotInCs))),
+        ("matches regex", Some((13, KeywordKind::MatchesRegex))),
+        ("matches", None),
+    ];
+
+    for (input, expected) in test_cases {
+        assert_eq!(
+            get_longest_keyword(input.as_bytes(), 0),
+            expected,
+            "{input}"
+        );
+    }
+}
+
+// ============ Incremental Tests ============
+
+fn apply_edit(old_text: &str, range: Range<usize>, replacement: &str) -> String {
+    let mut new_text = old_text.to_string();
+    new_text.replace_range(range, replacement);
+    new_text
+}
+
+fn assert_reparse_matches_full_parse(
+    old_text: &str,
+    range: Range<usize>,
+    replacement: &str,
+    options: &ParseOptions,
+) {
+    let previous = parse_tokens(old_text, options);
+    let new_text = apply_edit(old_text, range.clone(), replacement);
+    let edit = TextEdit {
+        range: range.clone(),
+        replacement,
+    };
+
+    assert_eq!(
+        reparse_tokens(&new_text, &previous, &edit, options),
+        parse_tokens(&new_text, options),
+        "{old_text:?} with {range:?} replaced by {replacement:?}"
+    );
+
+    let (_, previous_diagnostics) = parse_tokens_with_diagnostics(old_text, options);
+    let (_, diagnostics) = reparse_tokens_with_diagnostics(
+        &new_text,
+        &previous,
+        &previous_diagnostics,
+        &edit,
+        options,
+    );
+    assert_eq!(
+        diagnostics,
+        parse_tokens_with_diagnostics(&new_text, options).1,
+        "{old_text:?} with {range:?} replaced by {replacement:?}"
+    );
+}
+
+#[test]
+fn test_reparse_matches_full_parse() {
+    let documents = [
+        "T | where x > 1.\n.5 and y == 'a' // note\r\n| take 10\n",
+        "print ```multi\nline\nstring``` , ~~~other\n~~~ , 'x'\n| extend y = 1",
+        "let a = ```unclosed\nfoo bar\n| where datetime(2024-01-01) > now()",
+        "datatable(x:int)[int(\n1\n), 2]\n#directive here\n| project-away x*",
+        "h'secret' ¿ € @\"verb\"\"atim\" 123e4567-e89b-12d3-a456-426614174000  ",
+    ];
+    let replacements = [
+        "", "x", " ", "\n", "`", "```", "~~~", "'", ")", ".", "//", "(", "€",
+    ];
+    let option_sets = [
+        ParseOptions::default(),
+        ParseOptions::default().with_produce_trivia(true),
+        ParseOptions::default().with_allow_literals_with_line_breaks(true),
+        ParseOptions::default()
+            .with_always_produce_end_tokens(false)
+            .with_produce_trivia(true),
+    ];
+
+    for document in documents {
+        let boundaries: Vec<_> = (0..=document.len())
+            .filter(|&pos| document.is_char_boundary(pos))
+            .collect();
+
+        for options in &option_sets {
+            for (i, &start) in boundaries.iter().enumerate() {
+                for &end in boundaries[i..].iter().take(4) {
+                    for replacement in replacements {
+                        assert_reparse_matches_full_parse(
+                            document,
+                            start..end,
+                            replacement,
+                            options,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reparse_edit_inside_multi_line_string() {
+    let old_text = "print ```first\nsecond\nthird```\n| take 1";
+    let options = ParseOptions::default();
+    let previous = parse_tokens(old_text, &options);
+    let edit_pos = old_text.find("second").unwrap();
+    let new_text = apply_edit(old_text, edit_pos..edit_pos, "inserted ");
+    let edit = TextEdit {
+        range: edit_pos..edit_pos,
+        replacement: "inserted ",
+    };
+    let tokens = reparse_tokens(&new_text, &previous, &edit, &options);
+
+    assert_eq!(tokens, parse_tokens(&new_text, &options));
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::Literal(LiteralKind::String(
+            "```first\ninserted second\nthird```".into()
+        ))
+    );
+    assert_eq!(
+        tokens[4].span,
+        previous[4].span.start + 9..previous[4].span.end + 9
+    );
+}
+
+#[test]
+fn test_reparse_closing_multi_line_string() {
+    let old_text = "print ```open\nT | take 1\n| count";
+    let options = ParseOptions::default();
+    let previous = parse_tokens(old_text, &options);
+    assert_eq!(previous[1].kind, TokenKind::Bad("`".into()));
+
+    let new_text = apply_edit(old_text, old_text.len()..old_text.len(), "```");
+    let edit = TextEdit {
+        range: old_text.len()..old_text.len(),
+        replacement: "```",
+    };
+    let tokens = reparse_tokens(&new_text, &previous, &edit, &options);
+
+    assert_eq!(tokens, parse_tokens(&new_text, &options));
+    assert_eq!(tokens.len(), 3);
+    assert_eq!(
+        tokens[1].kind,
+        TokenKind::Literal(LiteralKind::String(new_text[6..].into()))
+    );
+}
+
+#[test]
+fn test_reparse_removing_multi_line_string_end() {
+    let old_text = "print ~~~a\nb~~~ | take 1";
+    let options = ParseOptions::default();
+    let previous = parse_tokens(old_text, &options);
+    let end = old_text.rfind("~~~").unwrap();
+
+    let new_text = apply_edit(old_text, end..end + 1, "");
+    let edit = TextEdit {
+        range: end..end + 1,
+        replacement: "",
+    };
+    let tokens = reparse_tokens(&new_text, &previous, &edit, &options);
+
+    assert_eq!(tokens, parse_tokens(&new_text, &options));
+    assert_eq!(tokens[1].kind, TokenKind::Bad("~".into()));
+}
+
+#[test]
+fn test_reparse_splices_diagnostics() {
+    let old_text = "print 'open\n| where x == ¿\n| take 1";
+    let options = ParseOptions::default();
+    let (previous, previous_diagnostics) = parse_tokens_with_diagnostics(old_text, &options);
+    assert_eq!(previous_diagnostics.len(), 2);
+
+    let end = old_text.find('\n').unwrap();
+    let new_text = apply_edit(old_text, end..end, "'");
+    let edit = TextEdit {
+        range: end..end,
+        replacement: "'",
+    };
+    let (tokens, diagnostics) = reparse_tokens_with_diagnostics(
+        &new_text,
+        &previous,
+        &previous_diagnostics,
+        &edit,
+        &options,
+    );
+
+    assert_eq!(tokens, parse_tokens(&new_text, &options));
+    assert_eq!(
+        diagnostics,
+        vec![LexDiagnostic {
+            kind: LexDiagnosticKind::InvalidCharacter,
+            span: previous_diagnostics[1].span.start + 1..previous_diagnostics[1].span.end + 1,
+            message: "invalid character '¿' (U+00BF)".to_string(),
+        }]
+    );
+}
+
+// ============ Tokenizer Tests ============
+
+#[test]
+fn test_tokenizer_matches_parse_tokens() {
+    let input = "T // comment\n| where x > 1.5 and s has 'a' ¿\n| take 10  ";
+    let option_sets = [
+        ParseOptions::default(),
+        ParseOptions::default().with_always_produce_end_tokens(false),
+        ParseOptions::default().with_produce_trivia(true),
+    ];
+
+    for options in option_sets {
+        let tokens: Vec<_> = Tokenizer::new(input, &options).collect();
+        assert_eq!(tokens, parse_tokens(input, &options), "{options:?}");
+    }
+}
+
+#[test]
+fn test_tokenizer_stops_early() {
+    let input = format!("T | where x > 1{}", " | extend y = 2".repeat(10_000));
+    let options = ParseOptions::default();
+    let mut tokenizer = Tokenizer::new(&input, &options);
+
+    let first_operator = tokenizer.by_ref().find_map(|token| match token.kind {
+        TokenKind::Keyword(kind) => Some(kind),
+        _ => None,
+    });
+
+    assert_eq!(first_operator, Some(KeywordKind::Where));
+    assert_eq!(
+        tokenizer.next().map(|t| t.kind),
+        Some(TokenKind::Identifier("x".into()))
+    );
+}
+
+#[test]
+fn test_tokenizer_is_fused() {
+    let options = ParseOptions::default();
+    let mut tokenizer = Tokenizer::new("+", &options);
+
+    assert_eq!(
+        tokenizer.next().map(|t| t.kind),
+        Some(TokenKind::Punctuation(PunctuationKind::Plus))
+    );
+    assert_eq!(tokenizer.next().map(|t| t.kind), Some(TokenKind::EndOfFile));
+    assert_eq!(tokenizer.next(), None);
+    assert_eq!(tokenizer.next(), None);
+}
+
+// ============ Diagnostic Tests ============
+
+fn get_diagnostics(input: &str) -> Vec<LexDiagnostic> {
+    parse_tokens_with_diagnostics(input, &ParseOptions::default()).1
+}
+
+#[test]
+fn test_diagnostics_valid_input() {
+    let input = "T | where s == 'a\\n' and d > datetime(2024-01-01) // ¿";
+    let (tokens, diagnostics) = parse_tokens_with_diagnostics(input, &ParseOptions::default());
+
+    assert_eq!(diagnostics, vec![]);
+    assert_eq!(tokens, parse_tokens(input, &ParseOptions::default()));
+}
+
+#[test]
+fn test_diagnostic_unterminated_string() {
+    assert_eq!(
+        get_diagnostics("x == 'abc\n| take 1"),
+        vec![LexDiagnostic {
+            kind: LexDiagnosticKind::UnterminatedString,
+            span: 5..9,
+            message: "unterminated string literal".to_string(),
+        }]
+    );
+
+    let diagnostics = get_diagnostics("@\"C:\\path");
+    assert_eq!(diagnostics[0].kind, LexDiagnosticKind::UnterminatedString);
+    assert_eq!(diagnostics[0].span, 0..9);
+}
+
+#[test]
+fn test_diagnostic_invalid_escape() {
+    let input = "'a\\qb' | take 1";
+    let (tokens, diagnostics) = parse_tokens_with_diagnostics(input, &ParseOptions::default());
+
+    assert_eq!(tokens[0].kind, TokenKind::Bad("'".into()));
+    assert_eq!(
+        diagnostics,
+        vec![
+            LexDiagnostic {
+                kind: LexDiagnosticKind::InvalidEscape,
+                span: 2..4,
+                message: "invalid escape sequence '\\q'".to_string(),
+            },
+            // The closing quote starts a new string that runs to the end of the line
+            LexDiagnostic {
+                kind: LexDiagnosticKind::UnterminatedString,
+                span: 5..15,
+                message: "unterminated string literal".to_string(),
+            },
+        ]
+    );
+
+    let diagnostics = get_diagnostics("'\\u12g4'");
+    assert_eq!(diagnostics[0].kind, LexDiagnosticKind::InvalidEscape);
+    assert_eq!(diagnostics[0].span, 1..3);
+}
+
+#[test]
+fn test_diagnostic_unterminated_multi_line_string() {
+    let diagnostics = get_diagnostics("print ```abc\ndef");
+
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0],
+        LexDiagnostic {
+            kind: LexDiagnosticKind::UnterminatedMultilineString,
+            span: 6..9,
+            message: "unterminated multi-line string literal, expected closing '```'".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_diagnostic_unterminated_literal() {
+    assert_eq!(
+        get_diagnostics("datetime(2024-01-01\n)"),
+        vec![LexDiagnostic {
+            kind: LexDiagnosticKind::UnterminatedLiteral,
+            span: 0..9,
+            message: "unterminated datetime literal, expected ')'".to_string(),
+        }]
+    );
+
+    let options = ParseOptions::default().with_allow_literals_with_line_breaks(true);
+    let (_, diagnostics) = parse_tokens_with_diagnostics("datetime(2024-01-01\n)", &options);
+    assert_eq!(diagnostics, vec![]);
+}
+
+#[test]
+fn test_diagnostic_invalid_character() {
+    assert_eq!(
+        get_diagnostics("a ¿ b ! c"),
+        vec![
+            LexDiagnostic {
+                kind: LexDiagnosticKind::InvalidCharacter,
+                span: 2..4,
+                message: "invalid character '¿' (U+00BF)".to_string(),
+            },
+            LexDiagnostic {
+                kind: LexDiagnosticKind::InvalidCharacter,
+                span: 7..8,
+                message: "unexpected character '!'".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_tokenizer_diagnostics() {
+    let options = ParseOptions::default();
+    let mut tokenizer = Tokenizer::new("1 ¿ 'x", &options);
+
+    tokenizer.next();
+    assert!(tokenizer.diagnostics().is_empty());
+
+    tokenizer.next();
+    assert_eq!(tokenizer.diagnostics().len(), 1);
+
+    tokenizer.by_ref().for_each(drop);
+    let kinds: Vec<_> = tokenizer.diagnostics().iter().map(|d| d.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            LexDiagnosticKind::InvalidCharacter,
+            LexDiagnosticKind::UnterminatedString
+        ]
+    );
+}
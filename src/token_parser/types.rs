@@ -1,11 +1,56 @@
+use std::{borrow::Cow, ops::Range};
+
 #[derive(Debug, PartialEq, Clone, Eq)]
-pub enum TokenKind {
+pub struct Token<'a> {
+    pub kind: TokenKind<'a>,
+    /// Byte range of the token text in the source.
+    pub span: Range<usize>,
+    /// Byte range of the whitespace and comments preceding the token.
+    pub trivia_span: Range<usize>,
+    /// Pieces of `trivia_span`, populated when `ParseOptions::produce_trivia` is set.
+    pub leading_trivia: Vec<Trivia>,
+    /// Trivia following the token up to and including the first line break, populated when
+    /// `ParseOptions::produce_trivia` is set.
+    pub trailing_trivia: Vec<Trivia>,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum TriviaKind {
+    Whitespace,
+    LineBreak,
+    Comment,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct LexDiagnostic {
+    pub kind: LexDiagnosticKind,
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, Eq)]
+pub enum LexDiagnosticKind {
+    UnterminatedString,
+    InvalidEscape,
+    UnterminatedMultilineString,
+    UnterminatedLiteral,
+    InvalidCharacter,
+}
+
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub enum TokenKind<'a> {
     Keyword(KeywordKind),
     Punctuation(PunctuationKind),
-    Literal(LiteralKind),
-    Identifier(String),
-    Directive(String),
-    Bad(String),
+    Literal(LiteralKind<'a>),
+    Identifier(Cow<'a, str>),
+    Directive(Cow<'a, str>),
+    Bad(Cow<'a, str>),
     EndOfFile,
 }
 
@@ -355,23 +400,76 @@ pub enum PunctuationKind {
 }
 
 #[derive(Debug, PartialEq, Clone, Eq)]
-pub enum LiteralKind {
-    Boolean(String),
-    DateTime(String),
-    Decimal(String),
-    Guid(String),
-    Int(String),
-    Long(String),
-    RawGuid(String),
-    Real(String),
-    Timespan(String),
-    String(String),
+pub enum LiteralKind<'a> {
+    Boolean(Cow<'a, str>),
+    DateTime(Cow<'a, str>),
+    Decimal(Cow<'a, str>),
+    Guid(Cow<'a, str>),
+    Int(Cow<'a, str>),
+    Long(Cow<'a, str>),
+    RawGuid(Cow<'a, str>),
+    Real(Cow<'a, str>),
+    Timespan(Cow<'a, str>),
+    String(Cow<'a, str>),
+}
+
+impl Token<'_> {
+    /// Byte range covering the token together with its leading and trailing trivia.
+    pub fn full_span(&self) -> Range<usize> {
+        let end = self
+            .trailing_trivia
+            .last()
+            .map_or(self.span.end, |trivia| trivia.span.end);
+        self.trivia_span.start..end
+    }
+
+    pub fn into_owned(self) -> Token<'static> {
+        Token {
+            kind: self.kind.into_owned(),
+            span: self.span,
+            trivia_span: self.trivia_span,
+            leading_trivia: self.leading_trivia,
+            trailing_trivia: self.trailing_trivia,
+        }
+    }
+}
+
+impl TokenKind<'_> {
+    pub fn into_owned(self) -> TokenKind<'static> {
+        match self {
+            TokenKind::Keyword(kind) => TokenKind::Keyword(kind),
+            TokenKind::Punctuation(kind) => TokenKind::Punctuation(kind),
+            TokenKind::Literal(kind) => TokenKind::Literal(kind.into_owned()),
+            TokenKind::Identifier(text) => TokenKind::Identifier(Cow::Owned(text.into_owned())),
+            TokenKind::Directive(text) => TokenKind::Directive(Cow::Owned(text.into_owned())),
+            TokenKind::Bad(text) => TokenKind::Bad(Cow::Owned(text.into_owned())),
+            TokenKind::EndOfFile => TokenKind::EndOfFile,
+        }
+    }
+}
+
+impl LiteralKind<'_> {
+    pub fn into_owned(self) -> LiteralKind<'static> {
+        match self {
+            LiteralKind::Boolean(text) => LiteralKind::Boolean(Cow::Owned(text.into_owned())),
+            LiteralKind::DateTime(text) => LiteralKind::DateTime(Cow::Owned(text.into_owned())),
+            LiteralKind::Decimal(text) => LiteralKind::Decimal(Cow::Owned(text.into_owned())),
+            LiteralKind::Guid(text) => LiteralKind::Guid(Cow::Owned(text.into_owned())),
+            LiteralKind::Int(text) => LiteralKind::Int(Cow::Owned(text.into_owned())),
+            LiteralKind::Long(text) => LiteralKind::Long(Cow::Owned(text.into_owned())),
+            LiteralKind::RawGuid(text) => LiteralKind::RawGuid(Cow::Owned(text.into_owned())),
+            LiteralKind::Real(text) => LiteralKind::Real(Cow::Owned(text.into_owned())),
+            LiteralKind::Timespan(text) => LiteralKind::Timespan(Cow::Owned(text.into_owned())),
+            LiteralKind::String(text) => LiteralKind::String(Cow::Owned(text.into_owned())),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct ParseOptions {
     pub always_produce_end_tokens: bool,
     pub allow_literals_with_line_breaks: bool,
+    pub produce_trivia: bool,
 }
 
 impl Default for ParseOptions {
@@ -379,6 +477,7 @@ impl Default for ParseOptions {
         Self {
             always_produce_end_tokens: true,
             allow_literals_with_line_breaks: false,
+            produce_trivia: false,
         }
     }
 }
@@ -393,4 +492,9 @@ impl ParseOptions {
         self.allow_literals_with_line_breaks = value;
         self
     }
+
+    pub fn with_produce_trivia(mut self, value: bool) -> Self {
+        self.produce_trivia = value;
+        self
+    }
 }
@@ -1,7 +1,4 @@
-use crate::token_parser::{
-    KeywordKind,
-    constants::{KEYWORDS, TIMESPAN_SUFFIXES},
-};
+use crate::token_parser::{KeywordKind, constants::*};
 
 #[inline(always)]
 pub(crate) fn peek(bytes: &[u8], pos: usize) -> Option<&u8> {
@@ -52,6 +49,25 @@ pub(crate) fn is_goo_literal_kind(keyword_kind: &KeywordKind) -> bool {
     )
 }
 
+/// Gets the length of the UTF-8 character at `pos` from its leading byte pattern.
+pub(crate) fn get_char_len(bytes: &[u8], pos: usize) -> usize {
+    let Some(&byte) = peek(bytes, pos) else {
+        return 1;
+    };
+
+    if byte & UTF8_1_BYTE_MASK == UTF8_1_BYTE_PATTERN {
+        1 // ASCII (0xxxxxxx)
+    } else if byte & UTF8_2_BYTE_MASK == UTF8_2_BYTE_PATTERN {
+        2 // 2-byte (110xxxxx)
+    } else if byte & UTF8_3_BYTE_MASK == UTF8_3_BYTE_PATTERN {
+        3 // 3-byte (1110xxxx)
+    } else if byte & UTF8_4_BYTE_MASK == UTF8_4_BYTE_PATTERN {
+        4 // 4-byte (11110xxx)
+    } else {
+        1 // Invalid UTF-8, treat as single byte
+    }
+}
+
 pub(crate) fn count_while<F>(bytes: &[u8], start: usize, predicate: F) -> usize
 where
     F: Fn(&u8) -> bool,
@@ -94,14 +110,7 @@ pub(crate) fn get_timespan_longest_suffix(bytes: &[u8], start: usize) -> Option<
 }
 
 pub(crate) fn get_longest_keyword(bytes: &[u8], start: usize) -> Option<(usize, KeywordKind)> {
-    for keyword in KEYWORDS {
-        let len = keyword.0.len();
-        if bytes.get(start..start + len) == Some(keyword.0) {
-            return Some((len, keyword.1));
-        }
-    }
-
-    None
+    KEYWORD_TRIE.longest_match(bytes, start)
 }
 
 fn get_line_len(bytes: &[u8], start: usize, include_line_break: bool) -> usize {